@@ -0,0 +1,50 @@
+//! Benchmarks for formatting versions, against the generic integer formatting path.
+
+#[macro_use]
+extern crate criterion;
+extern crate uhttp_version;
+
+use std::fmt::Write;
+
+use criterion::{black_box, Criterion};
+use uhttp_version::HttpVersion;
+
+/// Format the version through `core::fmt`'s integer formatting, as `Display` once did.
+struct Generic(HttpVersion);
+
+impl std::fmt::Display for Generic {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(fmt, "HTTP/{}.{}", self.0.major, self.0.minor)
+    }
+}
+
+fn bench_display(c: &mut Criterion) {
+    let mut group = c.benchmark_group("display");
+    let mut buf = String::with_capacity(64);
+
+    group.bench_function("generic", |b| b.iter(|| {
+        buf.clear();
+        write!(buf, "{}", Generic(black_box(HttpVersion::HTTP_11))).unwrap();
+    }));
+
+    group.bench_function("display", |b| b.iter(|| {
+        buf.clear();
+        write!(buf, "{}", black_box(HttpVersion::HTTP_11)).unwrap();
+    }));
+
+    group.bench_function("write_to", |b| b.iter(|| {
+        buf.clear();
+        black_box(HttpVersion::HTTP_11).write_to(&mut buf).unwrap();
+    }));
+
+    group.bench_function("status_line", |b| b.iter(|| {
+        buf.clear();
+        write!(buf, "{} {} {}\r\n", black_box(HttpVersion::HTTP_11), black_box(200),
+               black_box("OK")).unwrap();
+    }));
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_display);
+criterion_main!(benches);
@@ -0,0 +1,22 @@
+#![no_main]
+
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate uhttp_version;
+
+use uhttp_version::{HttpVersion, ParseOptions};
+
+fuzz_target!(|data: &[u8]| {
+    let ver = HttpVersion::from_bytes(data);
+
+    // The const parser must agree with the fast path, including on the error.
+    assert_eq!(HttpVersion::from_bytes_const(data), ver);
+
+    if let Ok(ver) = ver {
+        assert!(ver.is_valid());
+        assert_eq!(&ver.to_array()[..], data);
+        assert_eq!(format!("{}", ver).as_bytes(), data);
+    }
+
+    let _ = ParseOptions::LENIENT.parse(data);
+});
@@ -0,0 +1,37 @@
+#![no_main]
+
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate uhttp_version;
+
+use uhttp_version::{HttpVersion, ParseStatus, VersionParser};
+
+fuzz_target!(|data: &[u8]| {
+    let res = HttpVersion::from_bytes_prefix(data);
+
+    if let Ok((ver, rest)) = res {
+        assert_eq!(&data[..8], &ver.to_array()[..]);
+        assert_eq!(&data[8..], rest);
+    }
+
+    // Splitting the input anywhere must not change the result.
+    let split = data.first().map_or(0, |&b| b as usize % (data.len() + 1));
+    let (a, b) = data.split_at(split);
+    assert_eq!(HttpVersion::from_chunks(vec![a, b]), res.map(|(ver, _)| ver));
+
+    let mut parser = VersionParser::new();
+
+    for chunk in data.chunks(3) {
+        match parser.push(chunk) {
+            Ok(ParseStatus::NeedMore) => {},
+            Ok(ParseStatus::Done { version, .. }) => {
+                assert_eq!(HttpVersion::from_bytes(&data[..8]), Ok(version));
+                break;
+            },
+            Err(_) => {
+                assert!(HttpVersion::from_bytes(&data[..data.len().min(8)]).is_err());
+                break;
+            },
+        }
+    }
+});
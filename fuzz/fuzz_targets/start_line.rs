@@ -0,0 +1,38 @@
+#![no_main]
+
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate uhttp_version;
+
+use uhttp_version::{RequestLine, StartLine, StartLineSpans, StatusLine};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(line) = StartLine::from_bytes(data) {
+        assert!(line.version().is_valid());
+    }
+
+    if let Ok(line) = RequestLine::from_bytes(data) {
+        let _ = line.request_target();
+
+        let spans = StartLineSpans::from_request_line(data).unwrap();
+        assert_eq!(&data[spans.fields.0], line.method);
+        assert_eq!(&data[spans.fields.1], line.target);
+        assert_eq!(spans.version, line.version);
+    }
+
+    if let Ok(line) = StatusLine::from_bytes(data) {
+        // Formatting a parsed line must give a line that parses the same, except that
+        // invalid UTF-8 in the reason phrase is written as in `from_utf8_lossy`.
+        let s = format!("{}", line);
+        let parsed = StatusLine::from_bytes(s.as_bytes()).unwrap();
+
+        if line.reason_str().is_some() {
+            assert_eq!(parsed, line);
+        } else {
+            assert_eq!((parsed.version, parsed.code), (line.version, line.code));
+            assert_eq!(parsed.reason, String::from_utf8_lossy(line.reason).as_bytes());
+        }
+    }
+
+    let _ = StartLineSpans::from_status_line(data);
+});
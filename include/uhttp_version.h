@@ -0,0 +1,36 @@
+/* C interface to the uhttp_version parser and formatter, enabled by the `ffi` feature. */
+
+#ifndef UHTTP_VERSION_H
+#define UHTTP_VERSION_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define UHTTP_VERSION_OK 0
+#define UHTTP_VERSION_ERR_NULL (-1)
+#define UHTTP_VERSION_ERR_BAD_PREFIX (-2)
+#define UHTTP_VERSION_ERR_BAD_LENGTH (-3)
+#define UHTTP_VERSION_ERR_BAD_SEPARATOR (-4)
+#define UHTTP_VERSION_ERR_BAD_DIGIT (-5)
+#define UHTTP_VERSION_ERR_OUT_OF_RANGE (-6)
+#define UHTTP_VERSION_ERR_BUFFER_TOO_SMALL (-7)
+#define UHTTP_VERSION_ERR_OTHER (-8)
+
+/* Parse the `len` bytes at `s` as an `HTTP/x.y` field, storing its parts in `major` and
+ * `minor`. Returns UHTTP_VERSION_OK or a negative status code, in which case the parts
+ * are left untouched. */
+int uhttp_version_parse(const uint8_t *s, size_t len, uint8_t *major, uint8_t *minor);
+
+/* Write the `HTTP/x.y` field of the given version to the `len` bytes at `buf`, without a
+ * terminating NUL. Returns the number of bytes written or a negative status code. */
+int uhttp_version_format(uint8_t major, uint8_t minor, uint8_t *buf, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,43 @@
+//! Structured generation of versions for fuzzing.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use HttpVersion;
+
+/// Generates only versions with single-digit parts, as accepted by `from_bytes`.
+impl<'a> Arbitrary<'a> for HttpVersion {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(HttpVersion::from_parts(u.int_in_range(0..=9)?, u.int_in_range(0..=9)?))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (2, Some(2))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_arbitrary() {
+        let data: Vec<u8> = (0..=255).collect();
+        let mut u = Unstructured::new(&data);
+
+        while !u.is_empty() {
+            let ver = HttpVersion::arbitrary(&mut u).unwrap();
+            assert!(ver.major < 10 && ver.minor < 10);
+            assert_eq!(HttpVersion::from_bytes(&ver.to_array()), Ok(ver));
+        }
+
+        let ver = HttpVersion::arbitrary(&mut Unstructured::new(&[1, 1])).unwrap();
+        assert_eq!(HttpVersion::arbitrary(&mut Unstructured::new(&[1, 1])).unwrap(), ver);
+
+        let vers: Vec<HttpVersion> = Unstructured::new(&[3, 7, 200, 42, 9, 0])
+            .arbitrary_iter().unwrap()
+            .map(|ver| ver.unwrap())
+            .collect();
+        assert!(!vers.is_empty());
+        assert!(vers.iter().all(|ver| ver.major < 10 && ver.minor < 10));
+    }
+}
@@ -0,0 +1,261 @@
+//! Reading versions from asynchronous readers.
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+#[cfg(feature = "futures-io")]
+use futures_io::AsyncRead as FuturesAsyncRead;
+
+#[cfg(feature = "tokio")]
+use tokio::io::{AsyncRead as TokioAsyncRead, ReadBuf};
+
+use {HttpVersion, ParseStatus, ReadError, VersionParser};
+
+impl HttpVersion {
+    /// Create a future reading exactly the 8 bytes of a version field from the given
+    /// `tokio` reader and parsing them.
+    ///
+    /// Bytes of the `HTTP/` name are checked as they arrive, so input that isn't a version
+    /// field fails without waiting for the whole field. Nothing past the field is read.
+    #[cfg(feature = "tokio")]
+    pub fn from_async_read<'a, R>(reader: &'a mut R) -> ReadVersion<'a, R>
+        where R: TokioAsyncRead + Unpin + ?Sized
+    {
+        ReadVersion { reader, parser: VersionParser::new() }
+    }
+
+    /// Create a future reading exactly the 8 bytes of a version field from the given
+    /// `futures-io` reader and parsing them.
+    ///
+    /// Bytes of the `HTTP/` name are checked as they arrive, so input that isn't a version
+    /// field fails without waiting for the whole field. Nothing past the field is read.
+    #[cfg(feature = "futures-io")]
+    pub fn from_futures_read<'a, R>(reader: &'a mut R) -> FuturesReadVersion<'a, R>
+        where R: FuturesAsyncRead + Unpin + ?Sized
+    {
+        FuturesReadVersion { reader, parser: VersionParser::new() }
+    }
+}
+
+/// Future returned by `HttpVersion::from_async_read`.
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct ReadVersion<'a, R: ?Sized> {
+    /// Reader holding the field.
+    reader: &'a mut R,
+    /// Progress through the field across polls.
+    parser: VersionParser,
+}
+
+#[cfg(feature = "tokio")]
+impl<'a, R: TokioAsyncRead + Unpin + ?Sized> Future for ReadVersion<'a, R> {
+    type Output = Result<HttpVersion, ReadError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let reader = &mut *this.reader;
+
+        poll_version(&mut this.parser, |buf| {
+            let mut buf = ReadBuf::new(buf);
+            Pin::new(&mut *reader).poll_read(cx, &mut buf).map_ok(|()| buf.filled().len())
+        })
+    }
+}
+
+/// Future returned by `HttpVersion::from_futures_read`.
+#[cfg(feature = "futures-io")]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct FuturesReadVersion<'a, R: ?Sized> {
+    /// Reader holding the field.
+    reader: &'a mut R,
+    /// Progress through the field across polls.
+    parser: VersionParser,
+}
+
+#[cfg(feature = "futures-io")]
+impl<'a, R: FuturesAsyncRead + Unpin + ?Sized> Future for FuturesReadVersion<'a, R> {
+    type Output = Result<HttpVersion, ReadError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let reader = &mut *this.reader;
+
+        poll_version(&mut this.parser, |buf| Pin::new(&mut *reader).poll_read(cx, buf))
+    }
+}
+
+/// Drive the given parser with bytes from the given read function until the field is
+/// complete, the read is pending, or either fails.
+fn poll_version<F>(parser: &mut VersionParser, mut read: F)
+    -> Poll<Result<HttpVersion, ReadError>>
+    where F: FnMut(&mut [u8]) -> Poll<io::Result<usize>>
+{
+    let mut buf = [0; 8];
+
+    loop {
+        let buf = &mut buf[..parser.remaining()];
+
+        let len = match read(buf) {
+            Poll::Ready(Ok(0)) => {
+                parser.reset();
+                return Poll::Ready(Err(ReadError::Io(io::ErrorKind::UnexpectedEof.into())));
+            },
+            Poll::Ready(Ok(len)) => len,
+            Poll::Ready(Err(ref err)) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Poll::Ready(Err(err)) => {
+                parser.reset();
+                return Poll::Ready(Err(ReadError::Io(err)));
+            },
+            Poll::Pending => return Poll::Pending,
+        };
+
+        match parser.push(&buf[..len]) {
+            Ok(ParseStatus::NeedMore) => {},
+            Ok(ParseStatus::Done { version, .. }) => return Poll::Ready(Ok(version)),
+            Err(err) => return Poll::Ready(Err(ReadError::Version(err))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+    use std::task::{Wake, Waker};
+    use HttpVersionError;
+
+    /// Waker that does nothing, since `block_on` polls in a loop regardless.
+    struct Noop;
+
+    impl Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// Poll the given future to completion, with readers that wake immediately.
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = Box::pin(fut);
+        let waker = Waker::from(Arc::new(Noop));
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+                return out;
+            }
+        }
+    }
+
+    /// Reader returning one byte per ready read, with a pending read before each.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        pending: bool,
+    }
+
+    impl<'a> Trickle<'a> {
+        fn new(data: &'a [u8]) -> Self {
+            Trickle { data, pending: false }
+        }
+
+        fn poll_byte(&mut self, cx: &mut Context) -> Poll<Option<u8>> {
+            self.pending = !self.pending;
+
+            if self.pending {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+
+            match self.data.split_first() {
+                Some((&b, rest)) => {
+                    self.data = rest;
+                    Poll::Ready(Some(b))
+                },
+                None => Poll::Ready(None),
+            }
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    impl<'a> TokioAsyncRead for Trickle<'a> {
+        fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf)
+            -> Poll<io::Result<()>>
+        {
+            self.poll_byte(cx).map(|b| {
+                if let Some(b) = b {
+                    buf.put_slice(&[b]);
+                }
+
+                Ok(())
+            })
+        }
+    }
+
+    #[cfg(feature = "futures-io")]
+    impl<'a> FuturesAsyncRead for Trickle<'a> {
+        fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8])
+            -> Poll<io::Result<usize>>
+        {
+            self.poll_byte(cx).map(|b| match b {
+                Some(b) => {
+                    buf[0] = b;
+                    Ok(1)
+                },
+                None => Ok(0),
+            })
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_from_async_read() {
+        let mut r = &b"HTTP/1.1 200 OK"[..];
+        assert_eq!(block_on(HttpVersion::from_async_read(&mut r)).unwrap(),
+                   HttpVersion::HTTP_11);
+        assert_eq!(r, b" 200 OK");
+
+        let mut r = Trickle::new(b"HTTP/4.2\r\n");
+        assert_eq!(block_on(HttpVersion::from_async_read(&mut r)).unwrap(),
+                   HttpVersion::from_parts(4, 2));
+        assert_eq!(r.data, b"\r\n");
+
+        let mut r = Trickle::new(b"HTTP/1.");
+        match block_on(HttpVersion::from_async_read(&mut r)) {
+            Err(ReadError::Io(ref err)) if err.kind() == io::ErrorKind::UnexpectedEof => {},
+            res => panic!("unexpected result {:?}", res),
+        }
+
+        let mut r = Trickle::new(b"HTTx/1.1");
+        match block_on(HttpVersion::from_async_read(&mut r)) {
+            Err(ReadError::Version(HttpVersionError::BadPrefix)) => {},
+            res => panic!("unexpected result {:?}", res),
+        }
+        assert_eq!(r.data, b"/1.1");
+    }
+
+    #[cfg(feature = "futures-io")]
+    #[test]
+    fn test_from_futures_read() {
+        let mut r = &b"HTTP/1.0 200 OK"[..];
+        assert_eq!(block_on(HttpVersion::from_futures_read(&mut r)).unwrap(),
+                   HttpVersion::HTTP_10);
+        assert_eq!(r, b" 200 OK");
+
+        let mut r = Trickle::new(b"HTTP/2.0");
+        assert_eq!(block_on(HttpVersion::from_futures_read(&mut r)).unwrap(),
+                   HttpVersion::HTTP_2);
+
+        let mut r = Trickle::new(b"");
+        match block_on(HttpVersion::from_futures_read(&mut r)) {
+            Err(ReadError::Io(ref err)) if err.kind() == io::ErrorKind::UnexpectedEof => {},
+            res => panic!("unexpected result {:?}", res),
+        }
+
+        let mut r = Trickle::new(b"HTTP/1-1");
+        match block_on(HttpVersion::from_futures_read(&mut r)) {
+            Err(ReadError::Version(HttpVersionError::BadSeparator)) => {},
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+}
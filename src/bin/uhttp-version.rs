@@ -0,0 +1,147 @@
+//! Validate version fields and start lines, printing one result per input.
+//!
+//! Each argument is validated, or each line of stdin if there are no arguments. A line's
+//! trailing `\r\n` or `\n` terminator is ignored. Inputs containing a space are parsed as
+//! start lines, and all others as bare version fields such as `HTTP/1.1`.
+//!
+//! Each result is printed as a tab-separated line, either `ok`, the major part, and the
+//! minor part, or `error`, a stable error code, and a description. The exit status is 0
+//! if every input was valid, 1 if any was invalid, and 2 if stdin couldn't be read.
+
+extern crate uhttp_version;
+
+use std::io::{self, BufRead, Write};
+use std::process;
+
+use uhttp_version::{HttpVersion, HttpVersionError, RequestLineError, StartLine,
+                    StartLineError, StatusLineError};
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut valid = true;
+
+    if args.is_empty() {
+        let stdin = io::stdin();
+        let mut stdin = stdin.lock();
+        let mut line = Vec::new();
+
+        loop {
+            line.clear();
+
+            match stdin.read_until(b'\n', &mut line) {
+                Ok(0) => break,
+                Ok(_) => valid &= report(&mut out, &line),
+                Err(err) => {
+                    eprintln!("uhttp-version: failed to read stdin: {}", err);
+                    process::exit(2);
+                },
+            }
+        }
+    } else {
+        for arg in args.iter() {
+            valid &= report(&mut out, arg.as_bytes());
+        }
+    }
+
+    process::exit(if valid { 0 } else { 1 });
+}
+
+/// Validate the given input and print its result, returning whether it was valid.
+fn report<W: Write>(out: &mut W, input: &[u8]) -> bool {
+    let (res, valid) = match validate(input) {
+        Ok(ver) => (writeln!(out, "ok\t{}\t{}", ver.major, ver.minor), true),
+        Err((code, msg)) => (writeln!(out, "error\t{}\t{}", code, msg), false),
+    };
+
+    if res.is_err() {
+        // Stdout was closed, so there's no one left to report to.
+        process::exit(2);
+    }
+
+    valid
+}
+
+/// Parse the version of the given input, or get the code and description of the
+/// reason it's invalid.
+fn validate(input: &[u8]) -> Result<HttpVersion, (&'static str, String)> {
+    let input = strip_terminator(input);
+
+    if input.contains(&b' ') {
+        StartLine::from_bytes(input)
+            .map(|line| line.version())
+            .map_err(|err| (start_line_code(err), err.to_string()))
+    } else {
+        HttpVersion::from_bytes(input).map_err(|err| (version_code(err), err.to_string()))
+    }
+}
+
+/// Strip a trailing `\r\n` or `\n` terminator from the given line.
+fn strip_terminator(s: &[u8]) -> &[u8] {
+    if s.ends_with(b"\r\n") {
+        &s[..s.len() - 2]
+    } else if s.ends_with(b"\n") {
+        &s[..s.len() - 1]
+    } else {
+        s
+    }
+}
+
+/// Get the stable error code of the given start line error.
+fn start_line_code(err: StartLineError) -> &'static str {
+    match err {
+        StartLineError::Request(RequestLineError::MissingSeparator) |
+        StartLineError::Status(StatusLineError::MissingSeparator) => "missing_separator",
+        StartLineError::Request(RequestLineError::BadMethod) => "bad_method",
+        StartLineError::Request(RequestLineError::BadTarget) => "bad_target",
+        StartLineError::Status(StatusLineError::BadStatusCode) => "bad_status_code",
+        StartLineError::Status(StatusLineError::BadReason) => "bad_reason",
+        StartLineError::Request(RequestLineError::Version(err)) |
+        StartLineError::Status(StatusLineError::Version(err)) => version_code(err),
+    }
+}
+
+/// Get the stable error code of the given version error.
+fn version_code(err: HttpVersionError) -> &'static str {
+    match err {
+        HttpVersionError::BadPrefix => "bad_prefix",
+        HttpVersionError::BadLength => "bad_length",
+        HttpVersionError::BadSeparator => "bad_separator",
+        HttpVersionError::BadDigit => "bad_digit",
+        HttpVersionError::OutOfRange => "out_of_range",
+        HttpVersionError::TooNew => "too_new",
+        HttpVersionError::Unsupported => "unsupported",
+        HttpVersionError::BufferTooSmall => "buffer_too_small",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(validate(b"HTTP/1.1"), Ok(HttpVersion::HTTP_11));
+        assert_eq!(validate(b"HTTP/2.0\r\n"), Ok(HttpVersion::HTTP_2));
+        assert_eq!(validate(b"GET / HTTP/1.0\n"), Ok(HttpVersion::HTTP_10));
+        assert_eq!(validate(b"HTTP/1.1 200 OK\r\n"), Ok(HttpVersion::HTTP_11));
+
+        assert_eq!(validate(b"http/1.1").unwrap_err().0, "bad_prefix");
+        assert_eq!(validate(b"HTTP/1.10").unwrap_err().0, "bad_length");
+        assert_eq!(validate(b"GET / HTTP/1-1").unwrap_err().0, "bad_separator");
+        assert_eq!(validate(b"GET /").unwrap_err().0, "missing_separator");
+        assert_eq!(validate(b"HTTP/1.1 999 Nope").unwrap_err().0, "bad_status_code");
+        assert_eq!(validate(b"").unwrap_err().0, "bad_prefix");
+    }
+
+    #[test]
+    fn test_report() {
+        let mut out = Vec::new();
+
+        assert!(report(&mut out, b"HTTP/4.2"));
+        assert!(!report(&mut out, b"HTTP/1.x"));
+        assert_eq!(String::from_utf8(out).unwrap(),
+                   "ok\t4\t2\nerror\tbad_digit\tHTTP version part isn't a decimal digit\n");
+    }
+}
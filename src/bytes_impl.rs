@@ -0,0 +1,74 @@
+//! Parsing from `bytes` buffers, which may be split across several chunks.
+
+use bytes::Buf;
+
+use {HttpVersion, HttpVersionError, ParseStatus, VersionParser};
+
+impl HttpVersion {
+    /// Try to parse an `HttpVersion` from the start of the given buffer, advancing the
+    /// buffer past it.
+    ///
+    /// This behaves like `from_bytes_prefix`, with the field allowed to straddle chunk
+    /// boundaries. On error, chunks before the one holding the offending byte may have
+    /// been consumed.
+    pub fn from_buf<B: Buf>(buf: &mut B) -> Result<Self, HttpVersionError> {
+        let mut parser = VersionParser::new();
+
+        loop {
+            let chunk = buf.chunk();
+
+            if chunk.is_empty() {
+                return Err(parser.finish());
+            }
+
+            match parser.push(chunk)? {
+                ParseStatus::NeedMore => {
+                    let len = chunk.len();
+                    buf.advance(len);
+                },
+                ParseStatus::Done { version, consumed } => {
+                    buf.advance(consumed);
+
+                    return match buf.chunk().first() {
+                        Some(b) if b.is_ascii_digit() => Err(HttpVersionError::BadLength),
+                        _ => Ok(version),
+                    };
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_buf() {
+        let line = b"HTTP/1.1 200 OK";
+
+        for split in 0..=line.len() {
+            let (a, b) = line.split_at(split);
+            let mut buf = a.chain(b);
+
+            assert_eq!(HttpVersion::from_buf(&mut buf), Ok(HttpVersion::HTTP_11));
+            assert_eq!(buf.remaining(), 7);
+            assert_eq!(buf.chunk().first(), Some(&b' '));
+        }
+
+        let mut buf = &b"HTTP/2.0"[..];
+        assert_eq!(HttpVersion::from_buf(&mut buf), Ok(HttpVersion::HTTP_2));
+        assert!(!buf.has_remaining());
+
+        for split in 0..=9 {
+            let (a, b) = b"HTTP/1.10".split_at(split);
+            assert_eq!(HttpVersion::from_buf(&mut a.chain(b)), Err(HttpVersionError::BadLength));
+        }
+
+        assert_eq!(HttpVersion::from_buf(&mut &b""[..]), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_buf(&mut (&b"HTTP/"[..]).chain(&b"1."[..])),
+                   Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_buf(&mut (&b"HT"[..]).chain(&b"tp/1.1"[..])),
+                   Err(HttpVersionError::BadPrefix));
+    }
+}
@@ -0,0 +1,115 @@
+//! Protocol capabilities of versions, collected from the capability predicates.
+
+use HttpVersion;
+
+/// Protocol capabilities of a version, as reported by the `HttpVersion` predicates.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct Capabilities {
+    /// Whether connections persist across requests by default.
+    pub persistent_connections: bool,
+    /// Whether message bodies may use the chunked transfer coding.
+    pub chunked_transfer: bool,
+    /// Whether clients may pipeline requests on a connection.
+    pub pipelining: bool,
+    /// Whether requests must carry a `Host` header.
+    pub host_header: bool,
+    /// Whether messages use binary framing.
+    pub binary_framing: bool,
+}
+
+impl HttpVersion {
+    /// Retrieve the protocol capabilities of the version.
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            persistent_connections: self.supports_persistent_connections(),
+            chunked_transfer: self.supports_chunked_transfer(),
+            pipelining: self.supports_pipelining(),
+            host_header: self.requires_host_header(),
+            binary_framing: self.is_binary_framing(),
+        }
+    }
+}
+
+/// Column headings of the capability table, after the version column.
+#[cfg(feature = "std")]
+const COLUMNS: [&str; 5] = ["persistent", "chunked", "pipelining", "host", "binary"];
+
+/// Render the capabilities of the well-known versions as an aligned ASCII table, with
+/// one row per version marking each capability with `x` or `-`.
+///
+/// This is meant for inspecting what the crate believes about each version, such as in
+/// generated documentation or while debugging.
+#[cfg(feature = "std")]
+pub fn capability_table() -> String {
+    let mut table = String::from("version ");
+
+    for col in COLUMNS.iter() {
+        table.push_str("  ");
+        table.push_str(col);
+    }
+
+    for ver in HttpVersion::known() {
+        let caps = ver.capabilities();
+        let marks = [caps.persistent_connections, caps.chunked_transfer, caps.pipelining,
+                     caps.host_header, caps.binary_framing];
+
+        table.push('\n');
+        table.push_str(ver.as_str().unwrap_or("HTTP/?.?"));
+
+        for (col, &mark) in COLUMNS.iter().zip(marks.iter()) {
+            table.push_str("  ");
+            table.push(if mark { 'x' } else { '-' });
+
+            for _ in 1..col.len() {
+                table.push(' ');
+            }
+        }
+
+        let len = table.trim_end().len();
+        table.truncate(len);
+    }
+
+    table.push('\n');
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_capabilities() {
+        assert_eq!(HttpVersion::HTTP_10.capabilities(), Capabilities {
+            persistent_connections: false,
+            chunked_transfer: false,
+            pipelining: false,
+            host_header: false,
+            binary_framing: false,
+        });
+
+        assert_eq!(HttpVersion::HTTP_2.capabilities(), Capabilities {
+            persistent_connections: true,
+            chunked_transfer: false,
+            pipelining: false,
+            host_header: false,
+            binary_framing: true,
+        });
+
+        let caps = HttpVersion::from_parts(1, 2).capabilities();
+        assert_eq!(caps, HttpVersion::HTTP_11.capabilities());
+        assert!(caps.chunked_transfer && caps.host_header);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_capability_table() {
+        let table = capability_table();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 1 + HttpVersion::known().len());
+        assert_eq!(lines[0], "version   persistent  chunked  pipelining  host  binary");
+        assert!(lines.contains(&"HTTP/1.0  -           -        -           -     -"));
+        assert!(lines.contains(&"HTTP/1.1  x           x        x           x     -"));
+        assert!(lines.contains(&"HTTP/2.0  x           -        -           -     x"));
+    }
+}
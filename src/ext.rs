@@ -0,0 +1,81 @@
+//! Extension trait for parsing versions directly from byte slices and strings.
+
+use {HttpVersion, HttpVersionError};
+
+/// Parsing of version fields as methods on `[u8]` and `str`, for use inside larger
+/// parsers.
+///
+/// ```rust
+/// use uhttp_version::{HttpVersion, ParseHttpVersion};
+///
+/// assert_eq!(b"HTTP/1.1".parse_http_version(), Ok(HttpVersion::HTTP_11));
+/// assert_eq!("HTTP/1.0 200 OK".parse_http_version_prefix(),
+///            Ok((HttpVersion::HTTP_10, " 200 OK")));
+/// ```
+pub trait ParseHttpVersion {
+    /// Try to parse the whole value as a version field, as with
+    /// `HttpVersion::from_bytes`.
+    fn parse_http_version(&self) -> Result<HttpVersion, HttpVersionError>;
+
+    /// Try to parse a version field from the start of the value, returning the version
+    /// along with the remainder, as with `HttpVersion::from_bytes_prefix`.
+    fn parse_http_version_prefix(&self) -> Result<(HttpVersion, &Self), HttpVersionError>;
+}
+
+impl ParseHttpVersion for [u8] {
+    fn parse_http_version(&self) -> Result<HttpVersion, HttpVersionError> {
+        HttpVersion::from_bytes(self)
+    }
+
+    fn parse_http_version_prefix(&self) -> Result<(HttpVersion, &Self), HttpVersionError> {
+        HttpVersion::from_bytes_prefix(self)
+    }
+}
+
+impl ParseHttpVersion for str {
+    fn parse_http_version(&self) -> Result<HttpVersion, HttpVersionError> {
+        HttpVersion::from_bytes(self.as_bytes())
+    }
+
+    fn parse_http_version_prefix(&self) -> Result<(HttpVersion, &Self), HttpVersionError> {
+        let (ver, rest) = HttpVersion::from_bytes_prefix(self.as_bytes())?;
+
+        // The field is all ASCII, so the remainder starts on a character boundary.
+        Ok((ver, &self[self.len() - rest.len()..]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_http_version() {
+        for major in 0..10 {
+            for minor in 0..10 {
+                let ver = HttpVersion::from_parts(major, minor);
+                let s = format!("{}", ver);
+
+                assert_eq!(s.parse_http_version(), Ok(ver));
+                assert_eq!(s.as_bytes().parse_http_version(), Ok(ver));
+            }
+        }
+
+        assert_eq!(b"http/1.1".parse_http_version(), Err(HttpVersionError::BadPrefix));
+        assert_eq!("HTTP/1.1 ".parse_http_version(), Err(HttpVersionError::BadLength));
+        assert_eq!(b"HTTP/1-1"[..].parse_http_version(), Err(HttpVersionError::BadSeparator));
+    }
+
+    #[test]
+    fn test_parse_http_version_prefix() {
+        assert_eq!(b"HTTP/1.1\r\n"[..].parse_http_version_prefix(),
+                   Ok((HttpVersion::HTTP_11, &b"\r\n"[..])));
+        assert_eq!("HTTP/2.0".parse_http_version_prefix(), Ok((HttpVersion::HTTP_2, "")));
+        assert_eq!("HTTP/1.1 200 Å".parse_http_version_prefix(),
+                   Ok((HttpVersion::HTTP_11, " 200 Å")));
+
+        assert_eq!("HTTP/1.12".parse_http_version_prefix(), Err(HttpVersionError::BadLength));
+        assert_eq!("Å".parse_http_version_prefix(), Err(HttpVersionError::BadPrefix));
+        assert_eq!(b"HTTP/1"[..].parse_http_version_prefix(), Err(HttpVersionError::BadLength));
+    }
+}
@@ -0,0 +1,156 @@
+//! C interface to the version parser and formatter.
+//!
+//! The functions are declared for C in `include/uhttp_version.h`. To link them into a C
+//! program, build a static library with
+//! `cargo rustc --release --features ffi --crate-type staticlib`.
+//!
+//! Each function returns a negative status code on failure.
+
+use core::ffi::c_int;
+use core::slice;
+
+use {HttpVersion, HttpVersionError};
+
+/// Success.
+pub const UHTTP_VERSION_OK: c_int = 0;
+/// A required pointer argument was null.
+pub const UHTTP_VERSION_ERR_NULL: c_int = -1;
+/// The field didn't begin with the `HTTP/` name.
+pub const UHTTP_VERSION_ERR_BAD_PREFIX: c_int = -2;
+/// The version number wasn't exactly `DIGIT "." DIGIT`.
+pub const UHTTP_VERSION_ERR_BAD_LENGTH: c_int = -3;
+/// The version parts weren't separated by a `.`.
+pub const UHTTP_VERSION_ERR_BAD_SEPARATOR: c_int = -4;
+/// A version part wasn't a decimal digit.
+pub const UHTTP_VERSION_ERR_BAD_DIGIT: c_int = -5;
+/// A version part wasn't a single decimal digit.
+pub const UHTTP_VERSION_ERR_OUT_OF_RANGE: c_int = -6;
+/// The output buffer was too small.
+pub const UHTTP_VERSION_ERR_BUFFER_TOO_SMALL: c_int = -7;
+/// Any other error.
+pub const UHTTP_VERSION_ERR_OTHER: c_int = -8;
+
+/// Get the status code for the given error.
+fn status(err: HttpVersionError) -> c_int {
+    match err {
+        HttpVersionError::BadPrefix => UHTTP_VERSION_ERR_BAD_PREFIX,
+        HttpVersionError::BadLength => UHTTP_VERSION_ERR_BAD_LENGTH,
+        HttpVersionError::BadSeparator => UHTTP_VERSION_ERR_BAD_SEPARATOR,
+        HttpVersionError::BadDigit => UHTTP_VERSION_ERR_BAD_DIGIT,
+        HttpVersionError::OutOfRange => UHTTP_VERSION_ERR_OUT_OF_RANGE,
+        HttpVersionError::BufferTooSmall => UHTTP_VERSION_ERR_BUFFER_TOO_SMALL,
+        HttpVersionError::TooNew | HttpVersionError::Unsupported => UHTTP_VERSION_ERR_OTHER,
+    }
+}
+
+/// Parse the `len` bytes at `s` as a version field, as with `HttpVersion::from_bytes`,
+/// storing its parts in `major` and `minor` on success.
+///
+/// Returns `UHTTP_VERSION_OK` or a negative status code, in which case the parts are
+/// left untouched.
+///
+/// # Safety
+///
+/// `s` must point to `len` readable bytes, and `major` and `minor` must be valid for
+/// writes. `s` may be null only if `len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn uhttp_version_parse(s: *const u8, len: usize, major: *mut u8,
+                                             minor: *mut u8) -> c_int
+{
+    if (s.is_null() && len != 0) || major.is_null() || minor.is_null() {
+        return UHTTP_VERSION_ERR_NULL;
+    }
+
+    let s = if len == 0 { &[][..] } else { slice::from_raw_parts(s, len) };
+
+    match HttpVersion::from_bytes(s) {
+        Ok(ver) => {
+            *major = ver.major;
+            *minor = ver.minor;
+            UHTTP_VERSION_OK
+        },
+        Err(err) => status(err),
+    }
+}
+
+/// Write the `HTTP/x.y` field of the given version to the `len` bytes at `buf`, without
+/// a terminating NUL.
+///
+/// Returns the number of bytes written, which is always 8, or a negative status code.
+///
+/// # Safety
+///
+/// `buf` must point to `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn uhttp_version_format(major: u8, minor: u8, buf: *mut u8,
+                                              len: usize) -> c_int
+{
+    if buf.is_null() {
+        return UHTTP_VERSION_ERR_NULL;
+    }
+
+    let ver = match HttpVersion::try_from_parts(major, minor) {
+        Ok(ver) => ver,
+        Err(err) => return status(err),
+    };
+
+    match ver.encode(slice::from_raw_parts_mut(buf, len)) {
+        Ok(len) => len as c_int,
+        Err(err) => status(err),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::ptr;
+
+    #[test]
+    fn test_parse() {
+        let (mut major, mut minor) = (0xFF, 0xFF);
+
+        for &(input, code) in [
+            (&b"HTTP/1.1"[..], UHTTP_VERSION_OK),
+            (b"http/1.1", UHTTP_VERSION_ERR_BAD_PREFIX),
+            (b"HTTP/1.10", UHTTP_VERSION_ERR_BAD_LENGTH),
+            (b"HTTP/1-1", UHTTP_VERSION_ERR_BAD_SEPARATOR),
+            (b"HTTP/1.x", UHTTP_VERSION_ERR_BAD_DIGIT),
+        ].iter() {
+            let ret = unsafe {
+                uhttp_version_parse(input.as_ptr(), input.len(), &mut major, &mut minor)
+            };
+            assert_eq!(ret, code);
+        }
+
+        assert_eq!((major, minor), (1, 1));
+
+        unsafe {
+            assert_eq!(uhttp_version_parse(ptr::null(), 0, &mut major, &mut minor),
+                       UHTTP_VERSION_ERR_BAD_PREFIX);
+            assert_eq!(uhttp_version_parse(ptr::null(), 8, &mut major, &mut minor),
+                       UHTTP_VERSION_ERR_NULL);
+            assert_eq!(uhttp_version_parse(b"HTTP/2.0".as_ptr(), 8, ptr::null_mut(), &mut minor),
+                       UHTTP_VERSION_ERR_NULL);
+            assert_eq!(uhttp_version_parse(b"HTTP/2.0".as_ptr(), 8, &mut major, ptr::null_mut()),
+                       UHTTP_VERSION_ERR_NULL);
+        }
+    }
+
+    #[test]
+    fn test_format() {
+        let mut buf = [b'#'; 9];
+
+        unsafe {
+            assert_eq!(uhttp_version_format(4, 2, buf.as_mut_ptr(), buf.len()), 8);
+            assert_eq!(&buf, b"HTTP/4.2#");
+
+            assert_eq!(uhttp_version_format(1, 1, buf.as_mut_ptr(), 7),
+                       UHTTP_VERSION_ERR_BUFFER_TOO_SMALL);
+            assert_eq!(&buf, b"HTTP/4.2#");
+
+            assert_eq!(uhttp_version_format(12, 34, buf.as_mut_ptr(), buf.len()),
+                       UHTTP_VERSION_ERR_OUT_OF_RANGE);
+            assert_eq!(uhttp_version_format(1, 1, ptr::null_mut(), 8), UHTTP_VERSION_ERR_NULL);
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Low-level HTTP grammar primitives [RFC9110§5.6], as used by the crate's own parsers.
+//!
+//! These are exposed so small parsers for other message elements, such as methods,
+//! header fields, or chunk sizes, can share the same byte classifications.
+
+/// Space [RFC5234§B.1].
+pub const SP: u8 = b' ';
+/// Horizontal tab [RFC5234§B.1].
+pub const HTAB: u8 = b'\t';
+/// Carriage return [RFC5234§B.1].
+pub const CR: u8 = b'\r';
+/// Line feed [RFC5234§B.1].
+pub const LF: u8 = b'\n';
+/// Line terminator ending the start line and each header field [RFC9112§2.1].
+pub const CRLF: &[u8] = b"\r\n";
+
+/// Check if the given byte is a decimal digit [RFC5234§B.1].
+pub const fn is_digit(b: u8) -> bool {
+    b.is_ascii_digit()
+}
+
+/// Convert the given ASCII digit to a numeric digit if it's within the correct range.
+pub const fn to_digit(b: u8) -> Option<u8> {
+    if b.is_ascii_digit() {
+        Some(b - b'0')
+    } else {
+        None
+    }
+}
+
+/// Check if the given byte is a token character [RFC9110§5.6.2].
+pub const fn is_tchar(b: u8) -> bool {
+    match b {
+        b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+' | b'-' | b'.' | b'^' | b'_' |
+        b'`' | b'|' | b'~' => true,
+        _ => b.is_ascii_alphanumeric(),
+    }
+}
+
+/// Check if the given bytes form a nonempty token [RFC9110§5.6.2].
+pub fn is_token(s: &[u8]) -> bool {
+    !s.is_empty() && s.iter().all(|&b| is_tchar(b))
+}
+
+/// Check if the given byte is a visible (printing) ASCII character [RFC5234§B.1].
+pub const fn is_vchar(b: u8) -> bool {
+    b.is_ascii_graphic()
+}
+
+/// Check if the given byte is optional whitespace, a space or tab [RFC9110§5.6.3].
+pub const fn is_ows(b: u8) -> bool {
+    b == SP || b == HTAB
+}
+
+/// Strip any leading and trailing spaces and tabs from the given bytes.
+pub fn trim_ows(mut s: &[u8]) -> &[u8] {
+    while let [b' ' | b'\t', ref rest @ ..] = *s {
+        s = rest;
+    }
+
+    while let [ref rest @ .., b' ' | b'\t'] = *s {
+        s = rest;
+    }
+
+    s
+}
+
+/// Check that the given bytes begin with the given literal, returning the bytes
+/// following it if so.
+///
+/// The literal is matched exactly, so this is suited to case-sensitive elements such as
+/// the `HTTP/` name or a `CRLF`.
+pub fn expect<'a>(s: &'a [u8], lit: &[u8]) -> Option<&'a [u8]> {
+    s.strip_prefix(lit)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_digits() {
+        for b in 0..=255u8 {
+            assert_eq!(is_digit(b), b"0123456789".contains(&b));
+            assert_eq!(to_digit(b).is_some(), is_digit(b));
+        }
+
+        assert_eq!(to_digit(b'0'), Some(0));
+        assert_eq!(to_digit(b'9'), Some(9));
+        assert_eq!(to_digit(b'a'), None);
+        assert_eq!(to_digit(b'/'), None);
+        assert_eq!(to_digit(b':'), None);
+    }
+
+    #[test]
+    fn test_tokens() {
+        assert!(is_tchar(b'!'));
+        assert!(is_tchar(b'~'));
+        assert!(is_tchar(b'Z'));
+        assert!(!is_tchar(SP));
+        assert!(!is_tchar(b'('));
+        assert!(!is_tchar(b'"'));
+        assert!(!is_tchar(0x80));
+
+        assert!(is_token(b"GET"));
+        assert!(is_token(b"h2c"));
+        assert!(!is_token(b""));
+        assert!(!is_token(b"a b"));
+
+        assert!(is_vchar(b'!'));
+        assert!(is_vchar(b'~'));
+        assert!(!is_vchar(SP));
+        assert!(!is_vchar(0x7F));
+    }
+
+    #[test]
+    fn test_whitespace() {
+        assert!(is_ows(SP));
+        assert!(is_ows(HTAB));
+        assert!(!is_ows(CR));
+        assert!(!is_ows(LF));
+
+        assert_eq!(trim_ows(b" \tabc \t"), b"abc");
+        assert_eq!(trim_ows(b"a b"), b"a b");
+        assert_eq!(trim_ows(b" \t "), b"");
+        assert_eq!(trim_ows(b""), b"");
+    }
+
+    #[test]
+    fn test_expect() {
+        assert_eq!(expect(b"HTTP/1.1", b"HTTP/"), Some(&b"1.1"[..]));
+        assert_eq!(expect(b"\r\nrest", CRLF), Some(&b"rest"[..]));
+        assert_eq!(expect(b"http/1.1", b"HTTP/"), None);
+        assert_eq!(expect(b"HTT", b"HTTP/"), None);
+        assert_eq!(expect(b"abc", b""), Some(&b"abc"[..]));
+    }
+}
@@ -0,0 +1,73 @@
+//! Conversions to and from the `http` crate's `Version`.
+
+use core::convert::TryFrom;
+
+use http::Version;
+
+use {HttpVersion, HttpVersionError};
+
+/// Converts every version `http::Version` currently defines, failing with
+/// `HttpVersionError::Unsupported` for any it may add in the future rather than
+/// relabelling it.
+impl TryFrom<Version> for HttpVersion {
+    type Error = HttpVersionError;
+
+    fn try_from(ver: Version) -> Result<Self, Self::Error> {
+        match ver {
+            Version::HTTP_09 => Ok(HttpVersion::HTTP_09),
+            Version::HTTP_10 => Ok(HttpVersion::HTTP_10),
+            Version::HTTP_11 => Ok(HttpVersion::HTTP_11),
+            Version::HTTP_2 => Ok(HttpVersion::HTTP_2),
+            Version::HTTP_3 => Ok(HttpVersion::HTTP_3),
+            _ => Err(HttpVersionError::Unsupported),
+        }
+    }
+}
+
+/// Converts the versions representable by `http::Version`, failing with
+/// `HttpVersionError::Unsupported` for any other.
+impl TryFrom<HttpVersion> for Version {
+    type Error = HttpVersionError;
+
+    fn try_from(ver: HttpVersion) -> Result<Self, Self::Error> {
+        match ver {
+            HttpVersion::HTTP_09 => Ok(Version::HTTP_09),
+            HttpVersion::HTTP_10 => Ok(Version::HTTP_10),
+            HttpVersion::HTTP_11 => Ok(Version::HTTP_11),
+            HttpVersion::HTTP_2 => Ok(Version::HTTP_2),
+            HttpVersion::HTTP_3 => Ok(Version::HTTP_3),
+            _ => Err(HttpVersionError::Unsupported),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_http_version() {
+        let pairs = [
+            (Version::HTTP_09, HttpVersion::HTTP_09),
+            (Version::HTTP_10, HttpVersion::HTTP_10),
+            (Version::HTTP_11, HttpVersion::HTTP_11),
+            (Version::HTTP_2, HttpVersion::HTTP_2),
+            (Version::HTTP_3, HttpVersion::HTTP_3),
+        ];
+
+        for &(theirs, ours) in &pairs {
+            assert_eq!(HttpVersion::try_from(theirs), Ok(ours));
+            assert_eq!(Version::try_from(ours), Ok(theirs));
+        }
+
+        assert_eq!(Version::try_from(HttpVersion::from_parts(1, 2)),
+                   Err(HttpVersionError::Unsupported));
+        assert_eq!(Version::try_from(HttpVersion::from_parts(2, 1)),
+                   Err(HttpVersionError::Unsupported));
+        assert_eq!(Version::try_from(HttpVersion::from_parts(4, 2)),
+                   Err(HttpVersionError::Unsupported));
+
+        let ver = HttpVersion::from_bytes(b"HTTP/1.1").unwrap();
+        assert_eq!(Version::try_from(ver), Ok(Version::HTTP_11));
+    }
+}
@@ -0,0 +1,202 @@
+//! Registry of well-known versions and their metadata.
+
+use HttpVersion;
+
+/// Metadata about a well-known version.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct VersionInfo {
+    /// The described version.
+    pub version: HttpVersion,
+    /// Number of the RFC currently defining or describing the version.
+    pub rfc: u16,
+    /// Whether the version is obsolete and shouldn't be used by new implementations.
+    pub obsolete: bool,
+    /// Whether messages use binary framing rather than the text-based message syntax.
+    pub binary_framing: bool,
+}
+
+/// Well-known versions, in ascending order.
+pub const KNOWN_VERSIONS: [VersionInfo; 5] = [
+    // HTTP/0.9 was never specified by an RFC, but is described in [RFC1945§1.1] and
+    // superseded by HTTP/1.0.
+    VersionInfo {
+        version: HttpVersion::HTTP_09,
+        rfc: 1945,
+        obsolete: true,
+        binary_framing: false,
+    },
+    VersionInfo {
+        version: HttpVersion::HTTP_10,
+        rfc: 1945,
+        obsolete: false,
+        binary_framing: false,
+    },
+    VersionInfo {
+        version: HttpVersion::HTTP_11,
+        rfc: 9112,
+        obsolete: false,
+        binary_framing: false,
+    },
+    VersionInfo {
+        version: HttpVersion::HTTP_2,
+        rfc: 9113,
+        obsolete: false,
+        binary_framing: true,
+    },
+    VersionInfo {
+        version: HttpVersion::HTTP_3,
+        rfc: 9114,
+        obsolete: false,
+        binary_framing: true,
+    },
+];
+
+impl HttpVersion {
+    /// Retrieve the metadata of the version, if it's a well-known version.
+    pub fn info(&self) -> Option<VersionInfo> {
+        KNOWN_VERSIONS.iter().find(|info| info.version == *self).cloned()
+    }
+
+    /// Create an iterator over the well-known versions, in ascending order.
+    pub fn known() -> impl DoubleEndedIterator<Item = HttpVersion> + ExactSizeIterator {
+        KNOWN_VERSIONS.iter().map(|info| info.version)
+    }
+}
+
+/// Protocol generation of a version, for matching exhaustively on the well-known
+/// versions while still representing any other.
+///
+/// Converting from an `HttpVersion` produces `Other` only for versions that aren't
+/// well-known, so a well-known version should never be wrapped in `Other` directly.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum KnownVersion {
+    /// HTTP/0.9.
+    Http09,
+    /// HTTP/1.0.
+    Http10,
+    /// HTTP/1.1.
+    Http11,
+    /// HTTP/2.
+    Http2,
+    /// HTTP/3.
+    Http3,
+    /// Any version that isn't well-known.
+    Other(HttpVersion),
+}
+
+impl KnownVersion {
+    /// Retrieve the version represented by the generation.
+    pub const fn version(&self) -> HttpVersion {
+        match *self {
+            KnownVersion::Http09 => HttpVersion::HTTP_09,
+            KnownVersion::Http10 => HttpVersion::HTTP_10,
+            KnownVersion::Http11 => HttpVersion::HTTP_11,
+            KnownVersion::Http2 => HttpVersion::HTTP_2,
+            KnownVersion::Http3 => HttpVersion::HTTP_3,
+            KnownVersion::Other(ver) => ver,
+        }
+    }
+}
+
+impl From<HttpVersion> for KnownVersion {
+    fn from(ver: HttpVersion) -> Self {
+        match ver {
+            HttpVersion::HTTP_09 => KnownVersion::Http09,
+            HttpVersion::HTTP_10 => KnownVersion::Http10,
+            HttpVersion::HTTP_11 => KnownVersion::Http11,
+            HttpVersion::HTTP_2 => KnownVersion::Http2,
+            HttpVersion::HTTP_3 => KnownVersion::Http3,
+            _ => KnownVersion::Other(ver),
+        }
+    }
+}
+
+impl From<KnownVersion> for HttpVersion {
+    fn from(known: KnownVersion) -> Self {
+        known.version()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_info() {
+        let info = HttpVersion::HTTP_11.info().unwrap();
+        assert_eq!(info.version, HttpVersion::HTTP_11);
+        assert_eq!(info.rfc, 9112);
+        assert!(!info.obsolete);
+        assert!(!info.binary_framing);
+
+        assert!(HttpVersion::HTTP_09.info().unwrap().obsolete);
+        assert_eq!(HttpVersion::HTTP_3.info().unwrap().rfc, 9114);
+
+        assert_eq!(HttpVersion::from_parts(1, 2).info(), None);
+        assert_eq!(HttpVersion::from_parts(4, 2).info(), None);
+
+        for info in KNOWN_VERSIONS.iter() {
+            assert_eq!(info.version.info(), Some(*info));
+            assert_eq!(info.binary_framing, info.version.is_binary_framing());
+            assert!(info.version.as_str().is_some());
+        }
+    }
+
+    #[test]
+    fn test_known() {
+        let known: Vec<HttpVersion> = HttpVersion::known().collect();
+
+        assert_eq!(known, [HttpVersion::HTTP_09, HttpVersion::HTTP_10, HttpVersion::HTTP_11,
+                           HttpVersion::HTTP_2, HttpVersion::HTTP_3]);
+        assert_eq!(HttpVersion::known().len(), KNOWN_VERSIONS.len());
+        assert_eq!(HttpVersion::known().next_back(), Some(HttpVersion::HTTP_3));
+        assert!(known.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_known_version() {
+        assert_eq!(KnownVersion::from(HttpVersion::HTTP_11), KnownVersion::Http11);
+        assert_eq!(KnownVersion::from(HttpVersion::HTTP_2), KnownVersion::Http2);
+        assert_eq!(KnownVersion::from(HttpVersion::from_parts(1, 2)),
+                   KnownVersion::Other(HttpVersion::from_parts(1, 2)));
+        assert_eq!(HttpVersion::from(KnownVersion::Http09), HttpVersion::HTTP_09);
+
+        for ver in HttpVersion::known() {
+            match KnownVersion::from(ver) {
+                KnownVersion::Other(_) => panic!("well-known version {} classified as other", ver),
+                known => assert_eq!(known.version(), ver),
+            }
+        }
+
+        for major in 0..=255 {
+            for &minor in [0, 1, 9, 10, 255].iter() {
+                let ver = HttpVersion { major, minor };
+                assert_eq!(HttpVersion::from(KnownVersion::from(ver)), ver);
+            }
+        }
+    }
+
+    #[test]
+    fn test_known_agree() {
+        let known: Vec<HttpVersion> = HttpVersion::known().collect();
+
+        for (idx, &ver) in known.iter().enumerate() {
+            let s = ver.as_str().unwrap();
+            assert_eq!(HttpVersion::from_bytes(s.as_bytes()), Ok(ver));
+            assert_eq!(ver.to_string(), s);
+
+            let id = ver.as_alpn().unwrap();
+            assert_eq!(HttpVersion::from_alpn(id), Some(ver));
+
+            let class = KnownVersion::from(ver);
+            assert_eq!(class.version(), ver);
+            assert_eq!(class, KnownVersion::from(HttpVersion::from_bytes(s.as_bytes()).unwrap()));
+
+            for &other in &known[idx + 1..] {
+                assert_ne!(KnownVersion::from(other), class, "{} and {}", ver, other);
+                assert_ne!(other.as_str(), Some(s));
+                assert_ne!(other.as_alpn(), Some(id));
+            }
+        }
+    }
+}
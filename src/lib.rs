@@ -1,6 +1,12 @@
 //! This crate provides a parser/formatter for the [HTTP version
 //! field](https://tools.ietf.org/html/rfc7230#section-2.6) found in the
 //! request/response [start line](https://tools.ietf.org/html/rfc7230#section-3.1).
+//! Building on the version field, it also provides zero-allocation parsers for the
+//! complete [request line](https://www.rfc-editor.org/rfc/rfc9112#section-3) and
+//! [status line](https://www.rfc-editor.org/rfc/rfc9112#section-4), along with `HttpMethod`,
+//! `RequestTarget`, and `StatusCode` types for their fields.
+//! The same version syntax is available for protocols such as RTSP, SIP, and ICAP
+//! through `ProtocolVersion`.
 //!
 //! ## Example
 //!
@@ -23,15 +29,228 @@
 //! let ver = HttpVersion::from_bytes(b"HTTP/1.1").unwrap();
 //! assert_eq!(ver.major, 1);
 //! assert_eq!(ver.minor, 1);
+//! assert!(ver >= HttpVersion::from_parts(1, 0));
 //!
 //! let ver = HttpVersion::from_parts(4, 2);
 //! let mut buf = [b'#'; 8];
 //! write!(&mut buf[..], "{}", ver).unwrap();
 //! assert_eq!(&buf[..], b"HTTP/4.2");
 //! ```
+//!
+//! ## Panics
+//!
+//! Public functions are written not to panic on any input, including versions built with
+//! parts that aren't single digits. The only panic is the compile-time error `http_version!`
+//! raises for a malformed literal. `tests/no_panic.rs` checks this for the parsing entry
+//! points with [no-panic](https://github.com/dtolnay/no-panic), which fails to link a
+//! function that might panic, and runs with `cargo test --release`.
+//!
+//! ## Features
+//!
+//! The `std` feature, enabled by default, implements `std::error::Error` for
+//! `HttpVersionError`, provides `HttpVersion::from_reader` for reading from an
+//! `std::io::Read`, and provides `capability_table` for rendering the capabilities of
+//! the well-known versions. Without it, the crate is `#![no_std]` and depends only on
+//! `core`.
+//!
+//! The `serde` feature implements `Serialize` and `Deserialize` for `HttpVersion`, using
+//! the `HTTP/x.y` string in human-readable formats and the `(major, minor)` tuple in
+//! compact binary formats. The `serde_compact` and `serde_helpers` modules provide
+//! alternative representations for fields using `#[serde(with = "...")]`.
+//!
+//! The `arbitrary` feature implements `arbitrary::Arbitrary` for `HttpVersion`,
+//! generating only well-formed versions for use in fuzz targets. It enables `std`, since
+//! `arbitrary` requires it.
+//!
+//! The `bytes` feature provides `HttpVersion::from_buf` for parsing from a
+//! `bytes::Buf` whose chunks may split the version field.
+//!
+//! The `cli` feature builds the `uhttp-version` binary, which validates version fields
+//! and start lines given as arguments or on stdin, printing the parsed parts or an error
+//! code for each in a tab-separated format for scripts.
+//!
+//! The `defmt` feature implements `defmt::Format` for `HttpVersion` and the error types,
+//! for logging on embedded targets.
+//!
+//! The `ffi` feature exports a C interface to the parser and formatter, declared in
+//! `include/uhttp_version.h`.
+//!
+//! The `futures-io` and `tokio` features provide `HttpVersion::from_futures_read` and
+//! `HttpVersion::from_async_read` for reading from the respective asynchronous readers.
+//! Both enable `std`.
+//!
+//! The `http` feature provides `TryFrom` conversions in both directions between
+//! `HttpVersion` and the `http` crate's `Version` type, failing for versions the other
+//! can't represent. It enables `std`, since `http` requires it.
+//!
+//! The `rkyv` feature implements `Archive`, `Serialize`, and `Deserialize` for
+//! `HttpVersion`, so archived versions can be compared and read without deserializing.
+//!
+//! The `schemars` feature implements `JsonSchema` for `HttpVersion`, describing the
+//! `HTTP/x.y` string used by human-readable serde formats.
+//!
+//! The `tracing` feature emits `tracing` events from the version field, request line, and
+//! status line parsers, recording the input length and any failure reason for diagnosing
+//! malformed input in production.
+//!
+//! The `zerocopy` feature implements the `zerocopy` traits for reading and writing
+//! `HttpVersion` in place as its two `[major, minor]` bytes. Any pair of bytes is
+//! accepted, so parts read this way may not be single digits.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(any(feature = "std", test))]
+extern crate core;
+
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+
+#[cfg(feature = "bytes")]
+extern crate bytes;
+
+#[cfg(feature = "futures-io")]
+extern crate futures_io;
+
+#[cfg(feature = "defmt")]
+extern crate defmt;
+
+#[cfg(feature = "http")]
+extern crate http;
+
+#[cfg(feature = "rkyv")]
+extern crate rkyv;
+
+#[cfg(feature = "schemars")]
+extern crate alloc;
+
+#[cfg(feature = "schemars")]
+extern crate schemars;
+
+#[cfg(feature = "tokio")]
+extern crate tokio;
+
+#[cfg(feature = "tracing")]
+extern crate tracing;
+
+#[cfg(feature = "zerocopy")]
+extern crate zerocopy;
+
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[cfg(test)]
+extern crate heapless;
+
+#[cfg(all(test, feature = "serde"))]
+extern crate bincode;
+
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
+#[cfg(all(test, feature = "serde"))]
+#[macro_use]
+extern crate serde_derive;
+
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+mod async_impl;
+
+#[cfg(feature = "bytes")]
+mod bytes_impl;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+pub mod grammar;
+
+#[cfg(feature = "http")]
+mod http_impl;
+
+#[cfg(feature = "rkyv")]
+mod rkyv_impl;
+
+#[cfg(feature = "schemars")]
+mod schemars_impl;
+
+#[cfg(feature = "serde")]
+mod serde_impl;
+
+#[cfg(feature = "tracing")]
+mod tracing_impl;
+
+mod capabilities;
+mod ext;
+mod known;
+mod method;
+mod options;
+mod preface;
+mod protocol;
+mod protocol_version;
+#[cfg(feature = "std")]
+mod reader;
+
+mod request_line;
+mod request_target;
+mod spans;
+mod start_line;
+mod status_code;
+mod status_line;
+mod version_parser;
+mod version_range;
+mod version_set;
+
+#[cfg(feature = "futures-io")]
+pub use async_impl::FuturesReadVersion;
+#[cfg(feature = "tokio")]
+pub use async_impl::ReadVersion;
+#[cfg(feature = "std")]
+pub use capabilities::capability_table;
+pub use capabilities::Capabilities;
+pub use ext::ParseHttpVersion;
+pub use known::{KNOWN_VERSIONS, KnownVersion, VersionInfo};
+pub use method::{ExtensionMethod, HttpMethod, HttpMethodError};
+pub use options::ParseOptions;
+pub use preface::{H2_PREFACE, PrefaceMatch};
+pub use protocol::{AltSvcProtocol, ProtocolError, ReceivedProtocol, UpgradeProtocol,
+                   UpgradeProtocols, VersionToken};
+pub use protocol_version::ProtocolVersion;
+#[cfg(feature = "std")]
+pub use reader::ReadError;
+pub use request_line::{RequestLine, RequestLineError};
+pub use request_target::{RequestTarget, RequestTargetError};
+pub use spans::{SpanError, StartLineSpans};
+pub use start_line::{StartLine, StartLineError};
+pub use status_code::{StatusCode, StatusCodeError};
+pub use status_line::{StatusLine, StatusLineError};
+pub use version_parser::{ParseStatus, VersionParser};
+pub use version_range::{VersionRange, VersionRangeError};
+pub use version_set::{VersionSet, VersionSetIter};
+
+#[cfg(feature = "serde")]
+pub mod serde_compact;
+
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
 
 /// HTTP start line version field [RFC7230§2.6].
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+///
+/// Versions are ordered lexically by `(major, minor)`, so `HTTP/0.9 < HTTP/1.0 <
+/// HTTP/1.1 < HTTP/2.0`. This ordering is purely numeric and doesn't imply that a greater
+/// version is more capable than a lesser one; use `is_more_capable_than` for that.
+///
+/// The layout is guaranteed to be the two bytes `[major, minor]`, with no padding.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+#[cfg_attr(feature = "zerocopy", derive(zerocopy::FromBytes, zerocopy::IntoBytes,
+    zerocopy::KnownLayout, zerocopy::Immutable, zerocopy::Unaligned))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "rkyv", rkyv(derive(Copy, Clone, Eq, PartialEq, Debug, Hash),
+    compare(PartialEq)))]
+#[repr(C)]
 pub struct HttpVersion {
     /// Major version number.
     pub major: u8,
@@ -40,63 +259,705 @@ pub struct HttpVersion {
 }
 
 impl HttpVersion {
+    /// HTTP/0.9 [RFC1945§1.1].
+    pub const HTTP_09: HttpVersion = HttpVersion::from_parts(0, 9);
+    /// HTTP/1.0 [RFC1945].
+    pub const HTTP_10: HttpVersion = HttpVersion::from_parts(1, 0);
+    /// HTTP/1.1 [RFC9112].
+    pub const HTTP_11: HttpVersion = HttpVersion::from_parts(1, 1);
+    /// HTTP/2 [RFC9113].
+    pub const HTTP_2: HttpVersion = HttpVersion::from_parts(2, 0);
+    /// HTTP/3 [RFC9114].
+    pub const HTTP_3: HttpVersion = HttpVersion::from_parts(3, 0);
+
     /// Create a new `HttpVersion` from the given major and minor version parts.
-    pub fn from_parts(major: u8, minor: u8) -> Self {
-        // Major and minor version numbers must be single digits [RFC7230§2.6].
-        debug_assert!(major < 10 && minor < 10);
+    ///
+    /// The parts aren't checked, so the version is well-formed only if both are single
+    /// digits [RFC7230§2.6]. Use `try_from_parts` to check them, such as for parts from
+    /// untrusted input.
+    pub const fn from_parts(major: u8, minor: u8) -> Self {
+        HttpVersion { major, minor }
+    }
+
+    /// Create a new `HttpVersion` from the given major and minor version parts, failing
+    /// if either isn't a single decimal digit.
+    pub const fn try_from_parts(major: u8, minor: u8) -> Result<Self, HttpVersionError> {
+        if major < 10 && minor < 10 {
+            Ok(HttpVersion { major, minor })
+        } else {
+            Err(HttpVersionError::OutOfRange)
+        }
+    }
 
+    /// Create a new `HttpVersion` from the given major and minor version parts without
+    /// checking them.
+    ///
+    /// # Safety
+    ///
+    /// Both parts must be single decimal digits. Code handling an `HttpVersion` may rely
+    /// on this to produce a well-formed field.
+    pub const unsafe fn from_parts_unchecked(major: u8, minor: u8) -> Self {
         HttpVersion { major, minor }
     }
 
+    /// Create a new `HttpVersion` from the given wider major and minor version parts,
+    /// failing if either isn't a single decimal digit.
+    pub fn from_parts_u16(major: u16, minor: u16) -> Result<Self, HttpVersionError> {
+        if major < 10 && minor < 10 {
+            Ok(HttpVersion::from_parts(major as u8, minor as u8))
+        } else {
+            Err(HttpVersionError::OutOfRange)
+        }
+    }
+
+    /// Check if both version parts are single decimal digits, as required for a
+    /// well-formed field.
+    pub const fn is_valid(&self) -> bool {
+        self.major < 10 && self.minor < 10
+    }
+
     /// Try to parse an `HttpVersion` from the given bytes in the form required by the
     /// request line [syntax](https://tools.ietf.org/html/rfc7230#section-2.6).
     ///
     /// This verifies the syntax is well-formed and extracts the version parts.
-    pub fn from_bytes(s: &[u8]) -> Result<Self, ()> {
-        // Name is case sensitive [RFC7230§2.6].
+    pub fn from_bytes(s: &[u8]) -> Result<Self, HttpVersionError> {
+        traced!(version, s, parse_version(s))
+    }
+
+    /// Try to parse an `HttpVersion` from the given bytes like `from_bytes`, but usable in
+    /// const contexts.
+    ///
+    /// This is slower than `from_bytes`, so prefer that at runtime. The `http_version!`
+    /// macro uses this to check version literals at compile time.
+    pub const fn from_bytes_const(s: &[u8]) -> Result<Self, HttpVersionError> {
         const NAME: &[u8] = b"HTTP/";
 
-        if !s.starts_with(NAME) {
-            return Err(());
+        if s.len() < NAME.len() {
+            return Err(HttpVersionError::BadPrefix);
+        }
+
+        let mut idx = 0;
+
+        while idx < NAME.len() {
+            if s[idx] != NAME[idx] {
+                return Err(HttpVersionError::BadPrefix);
+            }
+
+            idx += 1;
+        }
+
+        if s.len() != 8 {
+            return Err(HttpVersionError::BadLength);
+        }
+
+        if s[6] != b'.' {
+            return Err(HttpVersionError::BadSeparator);
+        }
+
+        if !s[5].is_ascii_digit() || !s[7].is_ascii_digit() {
+            return Err(HttpVersionError::BadDigit);
+        }
+
+        Ok(HttpVersion::from_parts(s[5] - b'0', s[7] - b'0'))
+    }
+
+    /// Try to parse an `HttpVersion` from the start of the given bytes, returning the
+    /// version along with the remaining bytes following it.
+    ///
+    /// The version must not be directly followed by another digit, since that would make
+    /// it a multi-digit version part. A multi-digit part fails with `BadLength` whether
+    /// it's the major or the minor part, as in `from_bytes`.
+    pub fn from_bytes_prefix(s: &[u8]) -> Result<(Self, &[u8]), HttpVersionError> {
+        // Length of the "HTTP/x.y" field.
+        const LEN: usize = 8;
+
+        let (field, rest) = s.split_at(s.len().min(LEN));
+
+        let ver = match HttpVersion::from_bytes(field) {
+            // A digit in place of the `.` makes the major part multi-digit, as in
+            // `HTTP/10.1`, which is a length error like a multi-digit minor part.
+            Err(HttpVersionError::BadSeparator) if is_multi_digit_major(field) => {
+                return Err(HttpVersionError::BadLength);
+            },
+            res => res?,
+        };
+
+        match rest.first() {
+            Some(b) if b.is_ascii_digit() => Err(HttpVersionError::BadLength),
+            _ => Ok((ver, rest)),
+        }
+    }
+
+    /// Determine the version of a request from the tail of its request line, following
+    /// the request target.
+    ///
+    /// An empty tail is an HTTP/0.9 simple request [RFC1945§4.1], which has no version
+    /// field. Otherwise, the tail must be a space followed by the version field.
+    pub fn from_request_line_tail(s: &[u8]) -> Result<Self, HttpVersionError> {
+        match s.split_first() {
+            None => Ok(HttpVersion::HTTP_09),
+            Some((&b' ', ver)) => HttpVersion::from_bytes(ver),
+            Some(_) => Err(HttpVersionError::BadPrefix),
         }
+    }
+
+    /// Parse an `HttpVersion` from a line ending in the version field, allowing a single
+    /// trailing `\r\n` or `\n` terminator.
+    ///
+    /// On success, returns the version along with the number of bytes consumed, including
+    /// any terminator.
+    pub fn from_line_counting(s: &[u8]) -> Result<(Self, usize), HttpVersionError> {
+        let field = s.strip_suffix(b"\r\n")
+            .or_else(|| s.strip_suffix(b"\n"))
+            .unwrap_or(s);
+
+        HttpVersion::from_bytes(field).map(|ver| (ver, s.len()))
+    }
 
-        let ver = &s[NAME.len()..];
+    /// Parse an `HttpVersion` from the given bytes as in `from_bytes`, additionally
+    /// rejecting any version greater than the given maximum.
+    pub fn from_bytes_max(s: &[u8], max: HttpVersion) -> Result<Self, HttpVersionError> {
+        let ver = HttpVersion::from_bytes(s)?;
 
-        if ver.len() != 3 || ver[1] != b'.' {
-            return Err(());
+        if ver > max {
+            return Err(HttpVersionError::TooNew);
         }
 
-        match (to_digit(ver[0]), to_digit(ver[2])) {
-            (Some(major), Some(minor)) => Ok(HttpVersion::from_parts(major, minor)),
-            _ => return Err(()),
+        Ok(ver)
+    }
+
+    /// Parse an `HttpVersion` from the given bytes as in `from_bytes`, falling back to
+    /// the given default if the bytes aren't a well-formed version.
+    ///
+    /// Malformed bytes are only compared against the field, without classifying the
+    /// error or emitting a `tracing` event, so falling back costs no more than parsing.
+    pub fn from_bytes_or(s: &[u8], default: HttpVersion) -> Self {
+        use core::convert::TryFrom;
+
+        match <[u8; 8]>::try_from(s) {
+            Ok(field) => parse_word(field).unwrap_or(default),
+            Err(_) => default,
         }
     }
+
+    /// Encode the version as the bytes of its `HTTP/x.y` field, without going through
+    /// `core::fmt`.
+    ///
+    /// The version parts must be single digits for the result to be well-formed.
+    pub const fn to_array(&self) -> [u8; 8] {
+        [b'H', b'T', b'T', b'P', b'/', b'0'.wrapping_add(self.major), b'.',
+         b'0'.wrapping_add(self.minor)]
+    }
+
+    /// Retrieve the `HTTP/x.y` field of the version as a static string, if it's a
+    /// well-known version.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match *self {
+            HttpVersion::HTTP_09 => Some("HTTP/0.9"),
+            HttpVersion::HTTP_10 => Some("HTTP/1.0"),
+            HttpVersion::HTTP_11 => Some("HTTP/1.1"),
+            HttpVersion::HTTP_2 => Some("HTTP/2.0"),
+            HttpVersion::HTTP_3 => Some("HTTP/3.0"),
+            _ => None,
+        }
+    }
+
+    /// Write the bytes of the version's `HTTP/x.y` field to the start of the given buffer,
+    /// returning the number of bytes written.
+    ///
+    /// The only possible failure is a buffer shorter than the 8-byte field, in which case
+    /// nothing is written.
+    pub fn encode(&self, buf: &mut [u8]) -> Result<usize, HttpVersionError> {
+        let field = self.to_array();
+
+        match buf.get_mut(..field.len()) {
+            Some(dest) => {
+                dest.copy_from_slice(&field);
+                Ok(field.len())
+            }
+            None => Err(HttpVersionError::BufferTooSmall),
+        }
+    }
+
+    /// Write the version's `HTTP/x.y` field to the given `core::fmt::Write` sink, such as
+    /// a `heapless::String` or a UART writer, without needing `std::io::Write`.
+    ///
+    /// Unlike formatting with `Display`, this ignores formatter flags and writes a valid
+    /// version with a single `write_str` call.
+    ///
+    /// ```rust
+    /// use std::fmt::Write;
+    /// use uhttp_version::HttpVersion;
+    ///
+    /// struct Uart(Vec<u8>);
+    ///
+    /// impl Write for Uart {
+    ///     fn write_str(&mut self, s: &str) -> std::fmt::Result {
+    ///         self.0.extend_from_slice(s.as_bytes());
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut uart = Uart(Vec::new());
+    /// HttpVersion::HTTP_11.write_to(&mut uart).unwrap();
+    /// assert_eq!(uart.0, b"HTTP/1.1");
+    /// ```
+    pub fn write_to<W: core::fmt::Write>(&self, w: &mut W) -> core::fmt::Result {
+        if !self.is_valid() {
+            return write!(w, "HTTP/{}.{}", self.major, self.minor);
+        }
+
+        let field = self.to_array();
+
+        // A valid field is all ASCII.
+        match core::str::from_utf8(&field) {
+            Ok(s) => w.write_str(s),
+            Err(_) => Err(core::fmt::Error),
+        }
+    }
+
+    /// Look up the version identified by the given ALPN protocol ID [RFC7301§6].
+    ///
+    /// Both `h2` and the cleartext `h2c` identify HTTP/2.
+    pub fn from_alpn(id: &[u8]) -> Option<Self> {
+        match id {
+            b"http/0.9" => Some(HttpVersion::HTTP_09),
+            b"http/1.0" => Some(HttpVersion::HTTP_10),
+            b"http/1.1" => Some(HttpVersion::HTTP_11),
+            b"h2" | b"h2c" => Some(HttpVersion::HTTP_2),
+            b"h3" => Some(HttpVersion::HTTP_3),
+            _ => None,
+        }
+    }
+
+    /// Retrieve the ALPN protocol ID [RFC7301§6] negotiated over TLS for this version, if
+    /// it has one.
+    pub fn as_alpn(&self) -> Option<&'static [u8]> {
+        match *self {
+            HttpVersion::HTTP_09 => Some(b"http/0.9"),
+            HttpVersion::HTTP_10 => Some(b"http/1.0"),
+            HttpVersion::HTTP_11 => Some(b"http/1.1"),
+            HttpVersion::HTTP_2 => Some(b"h2"),
+            HttpVersion::HTTP_3 => Some(b"h3"),
+            _ => None,
+        }
+    }
+
+    /// Pack the version into a `u16` as `major << 8 | minor`.
+    ///
+    /// Packed values order the same as their `(major, minor)` parts, so they work as keys
+    /// in a `HashMap<u16, T>` when only the packed form is at hand for a lookup.
+    ///
+    /// `HttpVersion` deliberately doesn't implement `Borrow<u16>`. `Borrow` requires the
+    /// borrowed `&u16` to hash and compare exactly like the version itself, but the
+    /// version is stored as two separate bytes with a `Hash` over each field, so there's
+    /// neither an in-memory `u16` to point to nor a matching hash. Maps needing lookups by
+    /// the packed value should be keyed by `to_u16()` instead.
+    pub const fn to_u16(&self) -> u16 {
+        (self.major as u16) << 8 | self.minor as u16
+    }
+
+    /// Unpack a version from a `u16` in the form produced by `to_u16`, failing if either
+    /// part isn't a single decimal digit.
+    pub fn from_u16(packed: u16) -> Result<Self, HttpVersionError> {
+        HttpVersion::from_parts_u16(packed >> 8, packed & 0xFF)
+    }
+
+    /// Create an HTTP/1.x version from its minor part, as reported by parsers such as
+    /// `httparse` that assume a major part of 1, failing if it isn't a single digit.
+    pub const fn from_http1_minor(minor: u8) -> Result<Self, HttpVersionError> {
+        HttpVersion::try_from_parts(1, minor)
+    }
+
+    /// Retrieve the minor part of an HTTP/1.x version, as expected by parsers such as
+    /// `httparse`.
+    ///
+    /// Fails with `HttpVersionError::Unsupported` if the major part isn't 1, and with
+    /// `HttpVersionError::OutOfRange` if the minor part isn't a single digit.
+    pub const fn http1_minor(&self) -> Result<u8, HttpVersionError> {
+        if self.major != 1 {
+            Err(HttpVersionError::Unsupported)
+        } else if self.minor >= 10 {
+            Err(HttpVersionError::OutOfRange)
+        } else {
+            Ok(self.minor)
+        }
+    }
+
+    /// Clamp this version into the window `[floor, max]`, downgrading versions above
+    /// `max` to `max` and raising versions below `floor` to `floor`.
+    ///
+    /// If `floor` is greater than `max`, the window is empty and `floor` is always
+    /// returned.
+    pub fn saturating_downgrade(self, max: HttpVersion, floor: HttpVersion) -> HttpVersion {
+        self.min(max).max(floor)
+    }
+
+    /// Check if connections persist across requests by default [RFC9112§9.3].
+    ///
+    /// This holds for HTTP/1.1 and later. HTTP/1.0 connections only persist through the
+    /// non-default `keep-alive` extension.
+    pub fn supports_persistent_connections(&self) -> bool {
+        self.is_http11_family() || self.is_binary_framing()
+    }
+
+    /// Check if message bodies may use the chunked transfer coding [RFC9112§7.1].
+    ///
+    /// This is specific to HTTP/1.1, since HTTP/2 and later have their own framing.
+    pub fn supports_chunked_transfer(&self) -> bool {
+        self.is_http11_family()
+    }
+
+    /// Check if clients may pipeline requests on a connection [RFC9112§9.3.2].
+    ///
+    /// HTTP/2 and later multiplex requests instead.
+    pub fn supports_pipelining(&self) -> bool {
+        self.is_http11_family()
+    }
+
+    /// Check if requests must carry a `Host` header [RFC9112§3.2].
+    ///
+    /// HTTP/2 and later carry the authority in the `:authority` pseudo-header instead.
+    pub fn requires_host_header(&self) -> bool {
+        self.is_http11_family()
+    }
+
+    /// Check if messages use binary framing rather than the text-based message syntax,
+    /// as in HTTP/2 [RFC9113] and HTTP/3 [RFC9114].
+    pub fn is_binary_framing(&self) -> bool {
+        self.major >= 2
+    }
+
+    /// Check if this is HTTP/1.1 or a later 1.x version, which recipients treat as
+    /// HTTP/1.1 [RFC9110§2.5].
+    fn is_http11_family(&self) -> bool {
+        self.major == 1 && self.minor >= 1
+    }
+
+    /// Choose the version to send in a response to a request with the given version,
+    /// where this is the highest version the server supports.
+    ///
+    /// Following [RFC9110§6.2], this is the highest version the server conforms to whose
+    /// major version isn't greater than the client's. The server is assumed to also
+    /// support every well-known version below its highest, so an HTTP/2 server answers
+    /// an HTTP/1.0 request with HTTP/1.1, but never a version above its own.
+    pub fn response_version(&self, client: HttpVersion) -> HttpVersion {
+        if client.major >= self.major {
+            return *self;
+        }
+
+        HttpVersion::known().rev()
+            .find(|ver| ver.major <= client.major)
+            .unwrap_or(*self)
+    }
+
+    /// Check if this version is strictly more capable than the given version.
+    ///
+    /// Capability is only a partial order and doesn't follow the version numbers:
+    /// HTTP/0.9, HTTP/1.0, and HTTP/1.1 each extend the previous one, but HTTP/2 and
+    /// HTTP/3 replace the text message syntax entirely, so they're incomparable with
+    /// the HTTP/1.x family and with each other. Any other version is incomparable with
+    /// every version.
+    pub fn is_more_capable_than(&self, other: &HttpVersion) -> bool {
+        match (text_rank(*self), text_rank(*other)) {
+            (Some(a), Some(b)) => a > b,
+            _ => false,
+        }
+    }
+}
+
+/// Position of the given version in the chain of text-based HTTP versions, where each
+/// version is a superset of the ones before it.
+fn text_rank(ver: HttpVersion) -> Option<u8> {
+    match (ver.major, ver.minor) {
+        (0, 9) => Some(0),
+        (1, 0) => Some(1),
+        (1, 1) => Some(2),
+        _ => None,
+    }
+}
+
+/// Parse the given version field, as in `HttpVersion::from_bytes`.
+fn parse_version(s: &[u8]) -> Result<HttpVersion, HttpVersionError> {
+    use core::convert::TryFrom;
+
+    // Fast path for the common case of a well-formed field, with the slower checks below
+    // only needed to classify the error.
+    if let Ok(field) = <[u8; 8]>::try_from(s) {
+        if let Some(ver) = parse_word(field) {
+            return Ok(ver);
+        }
+    }
+
+    parse_field(s)
 }
 
-/// Convert the given ASCII digit to a numeric digit if it's within the correct range.
-fn to_digit(b: u8) -> Option<u8> {
-    if b >= b'0' && b <= b'9' {
-        Some(b - b'0')
+/// Parse the given 8-byte version field with a single word comparison, returning `None`
+/// if it's malformed.
+fn parse_word(field: [u8; 8]) -> Option<HttpVersion> {
+    // Name and separator with the two digit positions masked out.
+    const MASK: u64 = u64::from_le_bytes([0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0xFF, 0]);
+    const PATTERN: u64 = u64::from_le_bytes(*b"HTTP/\0.\0");
+
+    let word = u64::from_le_bytes(field);
+    let major = field[5].wrapping_sub(b'0');
+    let minor = field[7].wrapping_sub(b'0');
+
+    // Non-short-circuiting to keep the checks branchless.
+    if (word & MASK == PATTERN) & (major < 10) & (minor < 10) {
+        Some(HttpVersion::from_parts(major, minor))
     } else {
         None
     }
 }
 
+/// Parse the given version field byte by byte, classifying any syntax error.
+fn parse_field(s: &[u8]) -> Result<HttpVersion, HttpVersionError> {
+    // Name is case sensitive [RFC7230§2.6].
+    const NAME: &[u8] = b"HTTP/";
+
+    match grammar::expect(s, NAME) {
+        Some(ver) => parse_number(ver),
+        None => Err(HttpVersionError::BadPrefix),
+    }
+}
+
+/// Check if the given version field starts with a major part of more than one digit.
+fn is_multi_digit_major(field: &[u8]) -> bool {
+    match field.get(5..7) {
+        Some(&[a, b]) => a.is_ascii_digit() && b.is_ascii_digit(),
+        _ => false,
+    }
+}
+
+/// Parse the `DIGIT "." DIGIT` version number following the name.
+fn parse_number(ver: &[u8]) -> Result<HttpVersion, HttpVersionError> {
+    let (major, minor) = match *ver {
+        [major, b'.', minor] => (major, minor),
+        [_, _, _] => return Err(HttpVersionError::BadSeparator),
+        _ => return Err(HttpVersionError::BadLength),
+    };
+
+    match (grammar::to_digit(major), grammar::to_digit(minor)) {
+        (Some(major), Some(minor)) => Ok(HttpVersion::from_parts(major, minor)),
+        _ => Err(HttpVersionError::BadDigit),
+    }
+}
+
 /// Writes the version string in the form required by the HTTP status line.
-impl std::fmt::Display for HttpVersion {
-    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(fmt, "HTTP/{}.{}", self.major, self.minor)
+///
+/// The alternate form (`{:#}`) writes only the version number, such as `1.1`. Both forms
+/// respect the width, fill, and alignment flags.
+///
+/// The output is a well-formed version field only if the version `is_valid`; otherwise
+/// the parts are written as they are, such as `HTTP/12.34`.
+///
+/// Formatting a version never fails by itself: any error returned comes solely from the
+/// underlying sink.
+impl core::fmt::Display for HttpVersion {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use core::fmt::Write;
+
+        // The alternate form skips the "HTTP/" name.
+        let start = if fmt.alternate() { 5 } else { 0 };
+
+        // Fast path for valid versions, which are always exactly the bytes of the field.
+        if self.is_valid() {
+            let field = self.to_array();
+
+            // A valid field is all ASCII.
+            if let Ok(s) = core::str::from_utf8(&field) {
+                return fmt.pad(&s[start..]);
+            }
+        }
+
+        let mut buf = StackBuf::default();
+
+        // Writing into the buffer can't fail, since it fits the longest version string.
+        let _ = write!(buf, "HTTP/{}.{}", self.major, self.minor);
+
+        fmt.pad(&buf.as_str()[start..])
+    }
+}
+
+/// Fixed buffer large enough to format any version string into, so it can be padded as
+/// a whole.
+#[derive(Default)]
+struct StackBuf {
+    /// Formatted bytes.
+    buf: [u8; 12],
+    /// Number of bytes in `buf`.
+    len: usize,
+}
+
+impl StackBuf {
+    /// Retrieve the formatted string.
+    fn as_str(&self) -> &str {
+        // Only whole strings are ever copied in, so this can't fail.
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or("")
+    }
+}
+
+impl core::fmt::Write for StackBuf {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let dest = self.buf.get_mut(self.len..self.len + s.len()).ok_or(core::fmt::Error)?;
+        dest.copy_from_slice(s.as_bytes());
+        self.len += s.len();
+
+        Ok(())
     }
 }
 
-impl std::str::FromStr for HttpVersion {
-    type Err = ();
+/// Errors that can occur when parsing, constructing, or encoding an `HttpVersion`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HttpVersionError {
+    /// The field didn't begin with the case-sensitive `HTTP/` name.
+    BadPrefix,
+    /// The version following the name wasn't exactly 3 bytes long.
+    BadLength,
+    /// The major and minor versions weren't separated by a `.`.
+    BadSeparator,
+    /// A major or minor version wasn't an ASCII decimal digit.
+    BadDigit,
+    /// A major or minor version part wasn't a single decimal digit.
+    OutOfRange,
+    /// The version was well-formed but greater than the allowed maximum.
+    TooNew,
+    /// The version has no equivalent in the requested representation.
+    Unsupported,
+    /// The buffer was too short to hold the encoded version.
+    BufferTooSmall,
+}
+
+impl core::fmt::Display for HttpVersionError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use self::HttpVersionError::*;
+
+        fmt.write_str(match *self {
+            BadPrefix => "HTTP version doesn't begin with HTTP/",
+            BadLength => "HTTP version has invalid length",
+            BadSeparator => "HTTP version parts aren't separated by '.'",
+            BadDigit => "HTTP version part isn't a decimal digit",
+            OutOfRange => "HTTP version part isn't a single digit",
+            TooNew => "HTTP version is greater than the allowed maximum",
+            Unsupported => "HTTP version isn't supported by the requested representation",
+            BufferTooSmall => "buffer is too small for the HTTP version",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HttpVersionError {}
+
+/// Displays an optional version, writing `-` in place of a missing version.
+///
+/// This is useful for log lines where the version may be absent, such as for HTTP/0.9
+/// requests without a version field.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct DisplayOpt(pub Option<HttpVersion>);
+
+impl core::fmt::Display for DisplayOpt {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.0 {
+            Some(ref ver) => core::fmt::Display::fmt(ver, fmt),
+            None => fmt.pad("-"),
+        }
+    }
+}
+
+/// Writes the version string as in `Display`.
+#[cfg(feature = "defmt")]
+impl defmt::Format for HttpVersion {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "HTTP/{=u8}.{=u8}", self.major, self.minor)
+    }
+}
+
+impl core::str::FromStr for HttpVersion {
+    type Err = HttpVersionError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         HttpVersion::from_bytes(s.as_bytes())
     }
 }
 
+impl<'a> core::convert::TryFrom<&'a str> for HttpVersion {
+    type Error = HttpVersionError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        HttpVersion::from_bytes(s.as_bytes())
+    }
+}
+
+impl<'a> core::convert::TryFrom<&'a [u8]> for HttpVersion {
+    type Error = HttpVersionError;
+
+    fn try_from(s: &'a [u8]) -> Result<Self, Self::Error> {
+        HttpVersion::from_bytes(s)
+    }
+}
+
+impl core::convert::TryFrom<[u8; 8]> for HttpVersion {
+    type Error = HttpVersionError;
+
+    fn try_from(s: [u8; 8]) -> Result<Self, Self::Error> {
+        HttpVersion::from_bytes(&s)
+    }
+}
+
+/// Converts the version into its packed `to_u16` form.
+impl From<HttpVersion> for u16 {
+    fn from(ver: HttpVersion) -> Self {
+        ver.to_u16()
+    }
+}
+
+/// Converts a version packed by `to_u16`, as in `from_u16`.
+impl core::convert::TryFrom<u16> for HttpVersion {
+    type Error = HttpVersionError;
+
+    fn try_from(packed: u16) -> Result<Self, Self::Error> {
+        HttpVersion::from_u16(packed)
+    }
+}
+
+/// Converts `(major, minor)` parts, failing with `HttpVersionError::OutOfRange` if
+/// either isn't a single decimal digit.
+impl core::convert::TryFrom<(u8, u8)> for HttpVersion {
+    type Error = HttpVersionError;
+
+    fn try_from((major, minor): (u8, u8)) -> Result<Self, Self::Error> {
+        HttpVersion::try_from_parts(major, minor)
+    }
+}
+
+/// Compares against `(major, minor)` parts, so guards like `ver >= (1, 1)` work directly.
+impl PartialEq<(u8, u8)> for HttpVersion {
+    fn eq(&self, other: &(u8, u8)) -> bool {
+        (self.major, self.minor) == *other
+    }
+}
+
+impl PartialEq<HttpVersion> for (u8, u8) {
+    fn eq(&self, other: &HttpVersion) -> bool {
+        other == self
+    }
+}
+
+/// Orders like `HttpVersion` itself, comparing the major parts and then the minor parts.
+impl PartialOrd<(u8, u8)> for HttpVersion {
+    fn partial_cmp(&self, other: &(u8, u8)) -> Option<core::cmp::Ordering> {
+        (self.major, self.minor).partial_cmp(other)
+    }
+}
+
+impl PartialOrd<HttpVersion> for (u8, u8) {
+    fn partial_cmp(&self, other: &HttpVersion) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(&(other.major, other.minor))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -134,27 +995,749 @@ mod test {
             minor: 1,
         }));
 
-        assert_eq!("http/1.1".parse::<HttpVersion>(), Err(()));
-
-        assert_eq!(HttpVersion::from_bytes(b"http/1.1"), Err(()));
-        assert_eq!(HttpVersion::from_bytes(b"Http/1.1"), Err(()));
-        assert_eq!(HttpVersion::from_bytes(b"HTTp/1.1"), Err(()));
-        assert_eq!(HttpVersion::from_bytes(b"PTTH/1.1"), Err(()));
-        assert_eq!(HttpVersion::from_bytes(b"HTTP/"), Err(()));
-        assert_eq!(HttpVersion::from_bytes(b"HTTP/1.1 "), Err(()));
-        assert_eq!(HttpVersion::from_bytes(b"HTTP/1. "), Err(()));
-        assert_eq!(HttpVersion::from_bytes(b"HTTP/@.@"), Err(()));
-        assert_eq!(HttpVersion::from_bytes(b"HTTP/1.10"), Err(()));
-        assert_eq!(HttpVersion::from_bytes(b"HTTP/10.1"), Err(()));
-        assert_eq!(HttpVersion::from_bytes(b"HTTP@1.1"), Err(()));
-        assert_eq!(HttpVersion::from_bytes(b"HTTP/1@1"), Err(()));
-        assert_eq!(HttpVersion::from_bytes(b"HTTP/1 1"), Err(()));
-        assert_eq!(HttpVersion::from_bytes(b"PTTHPTTHPTTH"), Err(()));
-        assert_eq!(HttpVersion::from_bytes(b""), Err(()));
+        assert_eq!("http/1.1".parse::<HttpVersion>(), Err(HttpVersionError::BadPrefix));
+
+        assert_eq!(HttpVersion::from_bytes(b"http/1.1"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_bytes(b"Http/1.1"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_bytes(b"HTTp/1.1"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_bytes(b"PTTH/1.1"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_bytes(b"HTTP/"), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_bytes(b"HTTP/1.1 "), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_bytes(b"HTTP/1. "), Err(HttpVersionError::BadDigit));
+        assert_eq!(HttpVersion::from_bytes(b"HTTP/@.@"), Err(HttpVersionError::BadDigit));
+        assert_eq!(HttpVersion::from_bytes(b"HTTP/1.10"), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_bytes(b"HTTP/10.1"), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_bytes(b"HTTP@1.1"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_bytes(b"HTTP/1@1"), Err(HttpVersionError::BadSeparator));
+        assert_eq!(HttpVersion::from_bytes(b"HTTP/1 1"), Err(HttpVersionError::BadSeparator));
+        assert_eq!(HttpVersion::from_bytes(b"PTTHPTTHPTTH"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_bytes(b""), Err(HttpVersionError::BadPrefix));
 
         let mut buf = [b'|'; 8];
         write!(&mut buf[..], "{}", HttpVersion::from_parts(1, 1)).unwrap();
         assert_eq!(&buf[..], b"HTTP/1.1");
         assert_eq!(HttpVersion::from_bytes(&buf[..]), Ok(HttpVersion::from_parts(1, 1)));
     }
+
+    #[test]
+    fn test_error() {
+        assert_eq!(HttpVersionError::BadSeparator.to_string(),
+                   "HTTP version parts aren't separated by '.'");
+        assert_eq!(HttpVersionError::BadPrefix.to_string(),
+                   "HTTP version doesn't begin with HTTP/");
+        assert_eq!(HttpVersionError::BadDigit.to_string(),
+                   "HTTP version part isn't a decimal digit");
+        assert_eq!(HttpVersionError::TooNew.to_string(),
+                   "HTTP version is greater than the allowed maximum");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_std_error() {
+        fn parse(s: &str) -> Result<HttpVersion, Box<dyn std::error::Error>> {
+            Ok(s.parse()?)
+        }
+
+        assert_eq!(parse("HTTP/1.1").unwrap(), HttpVersion::from_parts(1, 1));
+        assert_eq!(parse("HTTP/1-1").unwrap_err().to_string(),
+                   "HTTP version parts aren't separated by '.'");
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn test_defmt() {
+        fn assert_format<T: defmt::Format>() {}
+
+        assert_format::<HttpVersion>();
+        assert_format::<HttpVersionError>();
+        assert_format::<HttpMethodError>();
+        assert_format::<ProtocolError>();
+        assert_format::<RequestLineError>();
+        assert_format::<RequestTargetError>();
+        assert_format::<SpanError>();
+        assert_format::<StartLineError>();
+        assert_format::<StatusCodeError>();
+        assert_format::<StatusLineError>();
+        assert_format::<VersionRangeError>();
+    }
+
+    #[cfg(feature = "zerocopy")]
+    #[test]
+    fn test_zerocopy() {
+        use zerocopy::{FromBytes, IntoBytes};
+
+        assert_eq!(std::mem::size_of::<HttpVersion>(), 2);
+        assert_eq!(std::mem::align_of::<HttpVersion>(), 1);
+
+        assert_eq!(HttpVersion::HTTP_11.as_bytes(), &[1, 1]);
+        assert_eq!(HttpVersion::HTTP_09.as_bytes(), &[0, 9]);
+        assert_eq!(HttpVersion::ref_from_bytes(&[2, 0][..]).unwrap(), &HttpVersion::HTTP_2);
+        assert!(HttpVersion::ref_from_bytes(&[1][..]).is_err());
+
+        let buf = [1, 0, 1, 1, 3, 0, 0xFF];
+        let (vers, rest) = <[HttpVersion]>::ref_from_prefix_with_elems(&buf[..], 3).unwrap();
+        assert_eq!(vers, [HttpVersion::HTTP_10, HttpVersion::HTTP_11, HttpVersion::HTTP_3]);
+        assert_eq!(rest, &[0xFF]);
+
+        let mut buf = [0u8; 4];
+        HttpVersion::HTTP_2.write_to_prefix(&mut buf[..]).unwrap();
+        let (ver, _) = HttpVersion::mut_from_prefix(&mut buf[..]).unwrap();
+        ver.minor = 1;
+        assert_eq!(buf, [2, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_display_sink_errors() {
+        use std::fmt;
+
+        struct FailSink;
+
+        impl fmt::Write for FailSink {
+            fn write_str(&mut self, _: &str) -> fmt::Result {
+                Err(fmt::Error)
+            }
+        }
+
+        let ver = HttpVersion::from_parts(1, 1);
+
+        assert_eq!(fmt::write(&mut FailSink, format_args!("{}", ver)), Err(fmt::Error));
+        assert_eq!(fmt::write(&mut FailSink, format_args!("{}", DisplayOpt(None))),
+                   Err(fmt::Error));
+
+        let mut s = String::new();
+        assert_eq!(fmt::write(&mut s, format_args!("{}", ver)), Ok(()));
+        assert_eq!(s, "HTTP/1.1");
+
+        for major in 0..10 {
+            for minor in 0..10 {
+                let mut buf = [0; 8];
+                assert!(write!(&mut buf[..], "{}", HttpVersion::from_parts(major, minor)).is_ok());
+            }
+        }
+
+        let mut buf = [0; 7];
+        let err = write!(&mut buf[..], "{}", ver).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(&HttpVersion::HTTP_11.to_array(), b"HTTP/1.1");
+        assert_eq!(&HttpVersion::HTTP_09.to_array(), b"HTTP/0.9");
+        assert_eq!(&HttpVersion::from_parts(4, 2).to_array(), b"HTTP/4.2");
+
+        for major in 0..10 {
+            for minor in 0..10 {
+                let ver = HttpVersion::from_parts(major, minor);
+                assert_eq!(ver.to_array(), ver.to_string().as_bytes());
+                assert_eq!(HttpVersion::from_bytes(&ver.to_array()), Ok(ver));
+            }
+        }
+
+        let mut buf = [b'#'; 10];
+        assert_eq!(HttpVersion::HTTP_10.encode(&mut buf), Ok(8));
+        assert_eq!(&buf, b"HTTP/1.0##");
+
+        let mut buf = [b'#'; 8];
+        assert_eq!(HttpVersion::HTTP_2.encode(&mut buf), Ok(8));
+        assert_eq!(&buf, b"HTTP/2.0");
+
+        let mut buf = [b'#'; 7];
+        assert_eq!(HttpVersion::HTTP_11.encode(&mut buf), Err(HttpVersionError::BufferTooSmall));
+        assert_eq!(&buf, b"#######");
+        assert_eq!(HttpVersion::HTTP_11.encode(&mut []), Err(HttpVersionError::BufferTooSmall));
+    }
+
+    #[test]
+    fn test_as_str() {
+        assert_eq!(HttpVersion::HTTP_09.as_str(), Some("HTTP/0.9"));
+        assert_eq!(HttpVersion::HTTP_10.as_str(), Some("HTTP/1.0"));
+        assert_eq!(HttpVersion::HTTP_11.as_str(), Some("HTTP/1.1"));
+        assert_eq!(HttpVersion::HTTP_2.as_str(), Some("HTTP/2.0"));
+        assert_eq!(HttpVersion::HTTP_3.as_str(), Some("HTTP/3.0"));
+        assert_eq!(HttpVersion::from_parts(1, 2).as_str(), None);
+        assert_eq!(HttpVersion::from_parts(4, 2).as_str(), None);
+
+        for major in 0..10 {
+            for minor in 0..10 {
+                let ver = HttpVersion::from_parts(major, minor);
+
+                if let Some(s) = ver.as_str() {
+                    assert_eq!(s, ver.to_string());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_to() {
+        for major in 0..10 {
+            for minor in 0..10 {
+                let ver = HttpVersion::from_parts(major, minor);
+
+                let mut s = String::new();
+                ver.write_to(&mut s).unwrap();
+                assert_eq!(s, format!("{}", ver));
+
+                let mut s = heapless::String::<8>::new();
+                ver.write_to(&mut s).unwrap();
+                assert_eq!(s.as_str(), format!("HTTP/{}.{}", major, minor));
+
+                let mut s = heapless::String::<7>::new();
+                assert_eq!(ver.write_to(&mut s), Err(core::fmt::Error));
+            }
+        }
+
+        let mut s = String::new();
+        HttpVersion { major: 12, minor: 34 }.write_to(&mut s).unwrap();
+        assert_eq!(s, "HTTP/12.34");
+    }
+
+    #[test]
+    fn test_alpn() {
+        assert_eq!(HttpVersion::from_alpn(b"http/0.9"), Some(HttpVersion::HTTP_09));
+        assert_eq!(HttpVersion::from_alpn(b"http/1.0"), Some(HttpVersion::HTTP_10));
+        assert_eq!(HttpVersion::from_alpn(b"http/1.1"), Some(HttpVersion::HTTP_11));
+        assert_eq!(HttpVersion::from_alpn(b"h2"), Some(HttpVersion::HTTP_2));
+        assert_eq!(HttpVersion::from_alpn(b"h2c"), Some(HttpVersion::HTTP_2));
+        assert_eq!(HttpVersion::from_alpn(b"h3"), Some(HttpVersion::HTTP_3));
+
+        assert_eq!(HttpVersion::from_alpn(b"HTTP/1.1"), None);
+        assert_eq!(HttpVersion::from_alpn(b"H2"), None);
+        assert_eq!(HttpVersion::from_alpn(b"h3-29"), None);
+        assert_eq!(HttpVersion::from_alpn(b"spdy/3"), None);
+        assert_eq!(HttpVersion::from_alpn(b""), None);
+
+        assert_eq!(HttpVersion::HTTP_11.as_alpn(), Some(&b"http/1.1"[..]));
+        assert_eq!(HttpVersion::HTTP_2.as_alpn(), Some(&b"h2"[..]));
+        assert_eq!(HttpVersion::HTTP_3.as_alpn(), Some(&b"h3"[..]));
+        assert_eq!(HttpVersion::from_parts(1, 2).as_alpn(), None);
+        assert_eq!(HttpVersion::from_parts(4, 2).as_alpn(), None);
+
+        for &ver in &[HttpVersion::HTTP_09, HttpVersion::HTTP_10, HttpVersion::HTTP_11,
+                      HttpVersion::HTTP_2, HttpVersion::HTTP_3] {
+            assert_eq!(HttpVersion::from_alpn(ver.as_alpn().unwrap()), Some(ver));
+        }
+    }
+
+    #[test]
+    fn test_display_flags() {
+        let ver = HttpVersion::HTTP_11;
+
+        assert_eq!(format!("{:>12}", ver), "    HTTP/1.1");
+        assert_eq!(format!("{:<12}|", ver), "HTTP/1.1    |");
+        assert_eq!(format!("{:^12}", ver), "  HTTP/1.1  ");
+        assert_eq!(format!("{:*>10}", ver), "**HTTP/1.1");
+        assert_eq!(format!("{:4}", ver), "HTTP/1.1");
+        assert_eq!(format!("{:.4}", ver), "HTTP");
+
+        assert_eq!(format!("{:#}", ver), "1.1");
+        assert_eq!(format!("{:#}", HttpVersion::HTTP_09), "0.9");
+        assert_eq!(format!("{:>#5}", ver), "  1.1");
+        assert_eq!(format!("{:-<#5}|", HttpVersion::HTTP_2), "2.0--|");
+
+        assert_eq!(format!("{}", HttpVersion { major: 255, minor: 255 }), "HTTP/255.255");
+        assert_eq!(format!("{:#}", HttpVersion { major: 12, minor: 34 }), "12.34");
+
+        assert_eq!(format!("{:>10}", DisplayOpt(Some(ver))), "  HTTP/1.1");
+        assert_eq!(format!("{:>10}", DisplayOpt(None)), "         -");
+        assert_eq!(format!("{:#}", DisplayOpt(Some(ver))), "1.1");
+    }
+
+    #[test]
+    fn test_display_opt() {
+        assert_eq!(DisplayOpt(Some(HttpVersion::from_parts(1, 1))).to_string(), "HTTP/1.1");
+        assert_eq!(DisplayOpt(Some(HttpVersion::from_parts(0, 9))).to_string(), "HTTP/0.9");
+        assert_eq!(DisplayOpt(None).to_string(), "-");
+    }
+
+    #[test]
+    fn test_consts() {
+        static TABLE: [(HttpVersion, &str); 3] = [
+            (HttpVersion::HTTP_10, "legacy"),
+            (HttpVersion::HTTP_11, "current"),
+            (HttpVersion::from_parts(2, 0), "binary"),
+        ];
+
+        const V42: HttpVersion = HttpVersion::from_parts(4, 2);
+        assert_eq!(V42.major, 4);
+        assert_eq!(V42.minor, 2);
+
+        assert_eq!(HttpVersion::HTTP_09, HttpVersion::from_parts(0, 9));
+        assert_eq!(HttpVersion::HTTP_10, HttpVersion::from_parts(1, 0));
+        assert_eq!(HttpVersion::HTTP_11, HttpVersion::from_parts(1, 1));
+        assert_eq!(HttpVersion::HTTP_2, HttpVersion::from_parts(2, 0));
+        assert_eq!(HttpVersion::HTTP_3, HttpVersion::from_parts(3, 0));
+
+        assert_eq!(HttpVersion::from_bytes(b"HTTP/1.1"), Ok(HttpVersion::HTTP_11));
+        assert_eq!(TABLE[2].0, HttpVersion::HTTP_2);
+
+        let desc = match HttpVersion::from_bytes(b"HTTP/1.0").unwrap() {
+            HttpVersion::HTTP_09 => "0.9",
+            HttpVersion::HTTP_10 => "1.0",
+            HttpVersion::HTTP_11 => "1.1",
+            _ => "other",
+        };
+        assert_eq!(desc, "1.0");
+    }
+
+    #[test]
+    fn test_try_from_parts() {
+        for major in 0..10 {
+            for minor in 0..10 {
+                let ver = HttpVersion::from_parts(major, minor);
+
+                assert_eq!(HttpVersion::try_from_parts(major, minor), Ok(ver));
+                assert_eq!(unsafe { HttpVersion::from_parts_unchecked(major, minor) }, ver);
+                assert!(ver.is_valid());
+            }
+        }
+
+        assert_eq!(HttpVersion::try_from_parts(12, 34), Err(HttpVersionError::OutOfRange));
+        assert_eq!(HttpVersion::try_from_parts(10, 0), Err(HttpVersionError::OutOfRange));
+        assert_eq!(HttpVersion::try_from_parts(0, 10), Err(HttpVersionError::OutOfRange));
+        assert_eq!(HttpVersion::try_from_parts(255, 255), Err(HttpVersionError::OutOfRange));
+
+        const VER: Result<HttpVersion, HttpVersionError> = HttpVersion::try_from_parts(1, 1);
+        assert_eq!(VER, Ok(HttpVersion::HTTP_11));
+
+        let ver = HttpVersion { major: 12, minor: 34 };
+        assert!(!ver.is_valid());
+        assert_eq!(format!("{}", ver), "HTTP/12.34");
+    }
+
+    #[test]
+    fn test_from_parts_u16() {
+        assert_eq!(HttpVersion::from_parts_u16(1, 1), Ok(HttpVersion::from_parts(1, 1)));
+        assert_eq!(HttpVersion::from_parts_u16(0, 0), Ok(HttpVersion::from_parts(0, 0)));
+        assert_eq!(HttpVersion::from_parts_u16(9, 9), Ok(HttpVersion::from_parts(9, 9)));
+        assert_eq!(HttpVersion::from_parts_u16(10, 0), Err(HttpVersionError::OutOfRange));
+        assert_eq!(HttpVersion::from_parts_u16(0, 10), Err(HttpVersionError::OutOfRange));
+        assert_eq!(HttpVersion::from_parts_u16(0, 256), Err(HttpVersionError::OutOfRange));
+        assert_eq!(HttpVersion::from_parts_u16(257, 1), Err(HttpVersionError::OutOfRange));
+    }
+
+    #[test]
+    fn test_http1_minor() {
+        assert_eq!(HttpVersion::from_http1_minor(0), Ok(HttpVersion::HTTP_10));
+        assert_eq!(HttpVersion::from_http1_minor(1), Ok(HttpVersion::HTTP_11));
+        assert_eq!(HttpVersion::from_http1_minor(9), Ok(HttpVersion::from_parts(1, 9)));
+        assert_eq!(HttpVersion::from_http1_minor(10), Err(HttpVersionError::OutOfRange));
+
+        assert_eq!(HttpVersion::HTTP_10.http1_minor(), Ok(0));
+        assert_eq!(HttpVersion::HTTP_11.http1_minor(), Ok(1));
+        assert_eq!(HttpVersion::HTTP_09.http1_minor(), Err(HttpVersionError::Unsupported));
+        assert_eq!(HttpVersion::HTTP_2.http1_minor(), Err(HttpVersionError::Unsupported));
+        assert_eq!(HttpVersion { major: 1, minor: 10 }.http1_minor(),
+                   Err(HttpVersionError::OutOfRange));
+        assert_eq!(HttpVersion { major: 12, minor: 34 }.http1_minor(),
+                   Err(HttpVersionError::Unsupported));
+
+        for minor in 0..10 {
+            let ver = HttpVersion::from_http1_minor(minor).unwrap();
+            assert_eq!(ver.http1_minor(), Ok(minor));
+        }
+    }
+
+    #[test]
+    fn test_try_from() {
+        use std::convert::{TryFrom, TryInto};
+
+        assert_eq!(HttpVersion::try_from("HTTP/1.1"), Ok(HttpVersion::HTTP_11));
+        assert_eq!(HttpVersion::try_from("http/1.1"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::try_from(&b"HTTP/1.0"[..]), Ok(HttpVersion::HTTP_10));
+        assert_eq!(HttpVersion::try_from(&b"HTTP/1.10"[..]), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::try_from(*b"HTTP/2.0"), Ok(HttpVersion::HTTP_2));
+        assert_eq!(HttpVersion::try_from(*b"HTTP/2-0"), Err(HttpVersionError::BadSeparator));
+        assert_eq!(HttpVersion::try_from((1, 1)), Ok(HttpVersion::HTTP_11));
+        assert_eq!(HttpVersion::try_from((9, 9)), Ok(HttpVersion::from_parts(9, 9)));
+        assert_eq!(HttpVersion::try_from((10, 0)), Err(HttpVersionError::OutOfRange));
+        assert_eq!(HttpVersion::try_from((0, 255)), Err(HttpVersionError::OutOfRange));
+
+        let ver: Result<HttpVersion, _> = "HTTP/0.9".try_into();
+        assert_eq!(ver, Ok(HttpVersion::HTTP_09));
+    }
+
+    #[test]
+    fn test_from_bytes_const() {
+        const V11: Result<HttpVersion, HttpVersionError> =
+            HttpVersion::from_bytes_const(b"HTTP/1.1");
+        assert_eq!(V11, Ok(HttpVersion::HTTP_11));
+
+        for major in 0..10 {
+            for minor in 0..10 {
+                let s = format!("HTTP/{}.{}", major, minor);
+                assert_eq!(HttpVersion::from_bytes_const(s.as_bytes()),
+                           Ok(HttpVersion::from_parts(major, minor)));
+            }
+        }
+
+        let bad: &[&[u8]] = &[b"", b"HTTP", b"HTTP/", b"http/1.1", b"HTTP/1.10", b"HTTP/1",
+            b"HTTP/1-1", b"HTTP/a.1", b"HTTP/1.b", b" HTTP/1.1", b"HTTP/1.1\r\n", b"HTTP\\1.1"];
+
+        for s in bad {
+            assert_eq!(HttpVersion::from_bytes_const(s), HttpVersion::from_bytes(s));
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_or() {
+        let def = HttpVersion::from_parts(9, 9);
+
+        assert_eq!(HttpVersion::from_bytes_or(b"HTTP/1.1", def), HttpVersion::from_parts(1, 1));
+        assert_eq!(HttpVersion::from_bytes_or(b"HTTP/0.9", def), HttpVersion::from_parts(0, 9));
+        assert_eq!(HttpVersion::from_bytes_or(b"HTTP/1.0", def), HttpVersion::from_parts(1, 0));
+        assert_eq!(HttpVersion::from_bytes_or(b"http/1.1", def), def);
+        assert_eq!(HttpVersion::from_bytes_or(b"HTTP/1.10", def), def);
+        assert_eq!(HttpVersion::from_bytes_or(b"", def), def);
+        assert_eq!(HttpVersion::from_bytes_or(b"HTTP/@.@", HttpVersion::from_parts(4, 2)),
+                   HttpVersion::from_parts(4, 2));
+
+        // A default no parse can give, so every fallback is visible.
+        let def = HttpVersion::from_parts(12, 34);
+
+        for pos in 0..8 {
+            for b in 0..=255 {
+                let mut field = *b"HTTP/1.1";
+                field[pos] = b;
+
+                for len in 0..=field.len() {
+                    let s = &field[..len];
+                    assert_eq!(HttpVersion::from_bytes_or(s, def),
+                               HttpVersion::from_bytes(s).unwrap_or(def));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_max() {
+        let max = HttpVersion::from_parts(1, 1);
+
+        assert_eq!(HttpVersion::from_bytes_max(b"HTTP/1.0", max), Ok(HttpVersion::from_parts(1, 0)));
+        assert_eq!(HttpVersion::from_bytes_max(b"HTTP/0.9", max), Ok(HttpVersion::from_parts(0, 9)));
+        assert_eq!(HttpVersion::from_bytes_max(b"HTTP/1.1", max), Ok(max));
+        assert_eq!(HttpVersion::from_bytes_max(b"HTTP/1.2", max), Err(HttpVersionError::TooNew));
+        assert_eq!(HttpVersion::from_bytes_max(b"HTTP/2.0", max), Err(HttpVersionError::TooNew));
+        assert_eq!(HttpVersion::from_bytes_max(b"HTTP/9.9", max), Err(HttpVersionError::TooNew));
+        assert_eq!(HttpVersion::from_bytes_max(b"HTTP/1.10", max), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_bytes_max(b"http/1.0", max), Err(HttpVersionError::BadPrefix));
+    }
+
+    #[test]
+    fn test_parse_word() {
+        for i in 0..8 {
+            for b in 0..=255 {
+                let mut field = *b"HTTP/1.1";
+                field[i] = b;
+
+                assert_eq!(parse_word(field).ok_or(()), parse_field(&field).map_err(|_| ()));
+                assert_eq!(HttpVersion::from_bytes(&field), parse_field(&field));
+            }
+        }
+
+        for major in 0..=255 {
+            for minor in 0..=255 {
+                let field = [b'H', b'T', b'T', b'P', b'/', major, b'.', minor];
+                assert_eq!(parse_word(field).ok_or(()), parse_field(&field).map_err(|_| ()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_prefix() {
+        assert_eq!(HttpVersion::from_bytes_prefix(b"HTTP/1.1\r\nHost: a\r\n"),
+                   Ok((HttpVersion::HTTP_11, &b"\r\nHost: a\r\n"[..])));
+        assert_eq!(HttpVersion::from_bytes_prefix(b"HTTP/1.0 200 OK"),
+                   Ok((HttpVersion::HTTP_10, &b" 200 OK"[..])));
+        assert_eq!(HttpVersion::from_bytes_prefix(b"HTTP/2.0"),
+                   Ok((HttpVersion::HTTP_2, &b""[..])));
+
+        assert_eq!(HttpVersion::from_bytes_prefix(b"HTTP/1.10 "),
+                   Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_bytes_prefix(b"HTTP/1."), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_bytes_prefix(b"HTTP/"), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_bytes_prefix(b"HTTP/10.1"),
+                   Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_bytes_prefix(b"HTTP/10.1 200 OK"),
+                   Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_bytes_prefix(b"HTTP/100"), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_bytes_prefix(b"HTTP/1x.1"),
+                   Err(HttpVersionError::BadSeparator));
+
+        for s in [&b"HTTP/10.1"[..], b"HTTP/1.10", b"HTTP/1-1", b"HTTP/2.0"].iter() {
+            assert_eq!(HttpVersion::from_bytes_prefix(s).map(|(ver, _)| ver),
+                       HttpVersion::from_bytes(s));
+        }
+        assert_eq!(HttpVersion::from_bytes_prefix(b"HTTP/1-1\r\n"),
+                   Err(HttpVersionError::BadSeparator));
+        assert_eq!(HttpVersion::from_bytes_prefix(b"HTTP/a.1\r\n"),
+                   Err(HttpVersionError::BadDigit));
+        assert_eq!(HttpVersion::from_bytes_prefix(b"http/1.1\r\n"),
+                   Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_bytes_prefix(b"HTT"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_bytes_prefix(b""), Err(HttpVersionError::BadPrefix));
+    }
+
+    #[test]
+    fn test_from_request_line_tail() {
+        assert_eq!(HttpVersion::from_request_line_tail(b""), Ok(HttpVersion::HTTP_09));
+        assert_eq!(HttpVersion::from_request_line_tail(b" HTTP/1.1"), Ok(HttpVersion::HTTP_11));
+        assert_eq!(HttpVersion::from_request_line_tail(b" HTTP/1.0"), Ok(HttpVersion::HTTP_10));
+        assert_eq!(HttpVersion::from_request_line_tail(b" HTTP/0.9"), Ok(HttpVersion::HTTP_09));
+
+        assert_eq!(HttpVersion::from_request_line_tail(b" "), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_request_line_tail(b"HTTP/1.1"),
+                   Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_request_line_tail(b"  HTTP/1.1"),
+                   Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_request_line_tail(b" HTTP/1.1 "),
+                   Err(HttpVersionError::BadLength));
+    }
+
+    #[test]
+    fn test_from_line_counting() {
+        let v11 = HttpVersion::from_parts(1, 1);
+
+        assert_eq!(HttpVersion::from_line_counting(b"HTTP/1.1\r\n"), Ok((v11, 10)));
+        assert_eq!(HttpVersion::from_line_counting(b"HTTP/1.1\n"), Ok((v11, 9)));
+        assert_eq!(HttpVersion::from_line_counting(b"HTTP/1.1"), Ok((v11, 8)));
+        assert_eq!(HttpVersion::from_line_counting(b"HTTP/1.0\r\n"),
+                   Ok((HttpVersion::from_parts(1, 0), 10)));
+
+        assert_eq!(HttpVersion::from_line_counting(b"HTTP/1.1\r"), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_line_counting(b"HTTP/1.1\n\n"), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_line_counting(b"HTTP/1.1\r\n\r\n"), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_line_counting(b"HTTP/1.1 \r\n"), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_line_counting(b"\r\n"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_line_counting(b""), Err(HttpVersionError::BadPrefix));
+    }
+
+    #[test]
+    fn test_to_u16() {
+        use std::collections::HashMap;
+
+        assert_eq!(HttpVersion::from_parts(0, 9).to_u16(), 0x0009);
+        assert_eq!(HttpVersion::from_parts(1, 0).to_u16(), 0x0100);
+        assert_eq!(HttpVersion::from_parts(1, 1).to_u16(), 0x0101);
+        assert_eq!(HttpVersion::from_parts(9, 9).to_u16(), 0x0909);
+        assert!(HttpVersion::from_parts(1, 9).to_u16() < HttpVersion::from_parts(2, 0).to_u16());
+
+        let mut map = HashMap::new();
+        map.insert(HttpVersion::from_parts(1, 0).to_u16(), "http/1.0");
+        map.insert(HttpVersion::from_parts(1, 1).to_u16(), "http/1.1");
+
+        let ver = HttpVersion::from_parts(1, 1);
+        assert_eq!(map.get(&0x0101), Some(&"http/1.1"));
+        assert_eq!(map.get(&ver.to_u16()), map.get(&0x0101));
+        assert_eq!(map.get(&HttpVersion::from_parts(2, 0).to_u16()), None);
+    }
+
+    #[test]
+    fn test_saturating_downgrade() {
+        let v09 = HttpVersion::from_parts(0, 9);
+        let v10 = HttpVersion::from_parts(1, 0);
+        let v11 = HttpVersion::from_parts(1, 1);
+        let v2 = HttpVersion::from_parts(2, 0);
+
+        assert_eq!(v2.saturating_downgrade(v11, v10), v11);
+        assert_eq!(HttpVersion::from_parts(1, 9).saturating_downgrade(v11, v10), v11);
+        assert_eq!(v09.saturating_downgrade(v11, v10), v10);
+        assert_eq!(v10.saturating_downgrade(v11, v10), v10);
+        assert_eq!(v11.saturating_downgrade(v11, v10), v11);
+        assert_eq!(v11.saturating_downgrade(v2, v09), v11);
+        assert_eq!(v11.saturating_downgrade(v11, v11), v11);
+
+        assert_eq!(v09.saturating_downgrade(v10, v11), v11);
+        assert_eq!(v2.saturating_downgrade(v10, v11), v11);
+    }
+
+    #[test]
+    fn test_tuple_cmp() {
+        for major in 0..10 {
+            for minor in 0..10 {
+                let ver = HttpVersion::from_parts(major, minor);
+
+                for a in 0..10 {
+                    for b in 0..10 {
+                        let other = HttpVersion::from_parts(a, b);
+
+                        assert_eq!(ver == (a, b), ver == other);
+                        assert_eq!((a, b) == ver, other == ver);
+                        assert_eq!(ver.partial_cmp(&(a, b)), ver.partial_cmp(&other));
+                        assert_eq!((a, b).partial_cmp(&ver), other.partial_cmp(&ver));
+                    }
+                }
+            }
+        }
+
+        let ver = HttpVersion::HTTP_11;
+        assert!(ver == (1, 1));
+        assert!(ver != (1, 0));
+        assert!(ver >= (1, 1));
+        assert!(ver > (1, 0));
+        assert!(ver < (2, 0));
+        assert!((1, 0) < ver);
+        assert!((0, 9) <= HttpVersion::HTTP_09);
+        assert!(HttpVersion { major: 1, minor: 10 } > (1, 9));
+    }
+
+    #[test]
+    fn test_ord() {
+        let v09 = HttpVersion::from_parts(0, 9);
+        let v10 = HttpVersion::from_parts(1, 0);
+        let v11 = HttpVersion::from_parts(1, 1);
+        let v2 = HttpVersion::from_parts(2, 0);
+
+        assert!(v09 < v10);
+        assert!(v10 < v11);
+        assert!(v11 < v2);
+        assert!(HttpVersion::from_parts(1, 9) < v2);
+        assert!(HttpVersion::from_parts(0, 9) < HttpVersion::from_parts(1, 0));
+        assert!(v11 >= HttpVersion::from_parts(1, 1));
+        assert!(v11 <= HttpVersion::from_parts(1, 1));
+
+        assert_eq!(v11.cmp(&v11), std::cmp::Ordering::Equal);
+        assert_eq!(v10.max(v11), v11);
+        assert_eq!(v2.min(v11), v11);
+
+        let mut vers = [v2, v09, v11, HttpVersion::from_parts(1, 9), v10];
+        vers.sort();
+        assert_eq!(vers, [v09, v10, v11, HttpVersion::from_parts(1, 9), v2]);
+
+        for major in 0..10 {
+            for minor in 0..10 {
+                let a = HttpVersion::from_parts(major, minor);
+
+                for &b in &vers {
+                    assert_eq!(a.cmp(&b), (a.major, a.minor).cmp(&(b.major, b.minor)));
+                    assert_eq!(a.cmp(&b), a.to_u16().cmp(&b.to_u16()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ord_is_not_capability() {
+        let v09 = HttpVersion::from_parts(0, 9);
+        let v11 = HttpVersion::from_parts(1, 1);
+        let v2 = HttpVersion::from_parts(2, 0);
+        let v3 = HttpVersion::from_parts(3, 0);
+
+        assert!(v2 > v11);
+        assert!(!v2.is_more_capable_than(&v11));
+
+        assert!(v3 > v2);
+        assert!(!v3.is_more_capable_than(&v2));
+
+        assert!(v3 > v09);
+        assert!(!v3.is_more_capable_than(&v09));
+
+        assert!(HttpVersion::from_parts(1, 2) > v11);
+        assert!(!HttpVersion::from_parts(1, 2).is_more_capable_than(&v11));
+
+        assert!(v11 > v09);
+        assert!(v11.is_more_capable_than(&v09));
+    }
+
+    #[test]
+    fn test_predicates() {
+        let vers = [HttpVersion::HTTP_09, HttpVersion::HTTP_10, HttpVersion::HTTP_11,
+                    HttpVersion::from_parts(1, 2), HttpVersion::HTTP_2, HttpVersion::HTTP_3];
+
+        let persistent: Vec<_> = vers.iter().map(|v| v.supports_persistent_connections())
+            .collect();
+        assert_eq!(persistent, [false, false, true, true, true, true]);
+
+        let chunked: Vec<_> = vers.iter().map(|v| v.supports_chunked_transfer()).collect();
+        assert_eq!(chunked, [false, false, true, true, false, false]);
+
+        let pipelining: Vec<_> = vers.iter().map(|v| v.supports_pipelining()).collect();
+        assert_eq!(pipelining, [false, false, true, true, false, false]);
+
+        let host: Vec<_> = vers.iter().map(|v| v.requires_host_header()).collect();
+        assert_eq!(host, [false, false, true, true, false, false]);
+
+        let binary: Vec<_> = vers.iter().map(|v| v.is_binary_framing()).collect();
+        assert_eq!(binary, [false, false, false, false, true, true]);
+    }
+
+    #[test]
+    fn test_response_version() {
+        let v11 = HttpVersion::HTTP_11;
+
+        assert_eq!(v11.response_version(HttpVersion::HTTP_11), v11);
+        assert_eq!(v11.response_version(HttpVersion::HTTP_10), v11);
+        assert_eq!(v11.response_version(HttpVersion::from_parts(1, 2)), v11);
+        assert_eq!(v11.response_version(HttpVersion::HTTP_2), v11);
+        assert_eq!(v11.response_version(HttpVersion::from_parts(4, 2)), v11);
+        assert_eq!(v11.response_version(HttpVersion::HTTP_09), HttpVersion::HTTP_09);
+
+        let v10 = HttpVersion::HTTP_10;
+        assert_eq!(v10.response_version(HttpVersion::HTTP_11), v10);
+        assert_eq!(v10.response_version(HttpVersion::HTTP_10), v10);
+        assert_eq!(v10.response_version(HttpVersion::HTTP_09), HttpVersion::HTTP_09);
+
+        let v2 = HttpVersion::HTTP_2;
+        assert_eq!(v2.response_version(HttpVersion::HTTP_10), v11);
+        assert_eq!(v2.response_version(HttpVersion::HTTP_11), v11);
+        assert_eq!(v2.response_version(HttpVersion::HTTP_2), v2);
+        assert_eq!(v2.response_version(HttpVersion::HTTP_3), v2);
+
+        assert_eq!(HttpVersion::HTTP_3.response_version(HttpVersion::HTTP_2), v2);
+        assert_eq!(HttpVersion::from_parts(1, 2).response_version(v10),
+                   HttpVersion::from_parts(1, 2));
+    }
+
+    #[test]
+    fn test_from_u16() {
+        use std::convert::TryFrom;
+        use std::sync::atomic::{AtomicU16, Ordering};
+
+        assert_eq!(HttpVersion::from_u16(0x0101), Ok(HttpVersion::HTTP_11));
+        assert_eq!(HttpVersion::from_u16(0x0009), Ok(HttpVersion::HTTP_09));
+        assert_eq!(HttpVersion::from_u16(0x0300), Ok(HttpVersion::HTTP_3));
+        assert_eq!(HttpVersion::from_u16(0x0909), Ok(HttpVersion::from_parts(9, 9)));
+        assert_eq!(HttpVersion::from_u16(0x010A), Err(HttpVersionError::OutOfRange));
+        assert_eq!(HttpVersion::from_u16(0x0A01), Err(HttpVersionError::OutOfRange));
+        assert_eq!(HttpVersion::from_u16(0xFFFF), Err(HttpVersionError::OutOfRange));
+
+        for major in 0..10 {
+            for minor in 0..10 {
+                let ver = HttpVersion::from_parts(major, minor);
+                assert_eq!(HttpVersion::from_u16(ver.to_u16()), Ok(ver));
+                assert_eq!(HttpVersion::try_from(u16::from(ver)), Ok(ver));
+            }
+        }
+
+        let conn = AtomicU16::new(HttpVersion::HTTP_10.into());
+        conn.store(HttpVersion::HTTP_11.into(), Ordering::Relaxed);
+        assert_eq!(HttpVersion::try_from(conn.load(Ordering::Relaxed)), Ok(HttpVersion::HTTP_11));
+    }
+
+    #[test]
+    fn test_capability() {
+        let v09 = HttpVersion::from_parts(0, 9);
+        let v10 = HttpVersion::from_parts(1, 0);
+        let v11 = HttpVersion::from_parts(1, 1);
+        let v2 = HttpVersion::from_parts(2, 0);
+        let v3 = HttpVersion::from_parts(3, 0);
+
+        assert!(v11.is_more_capable_than(&v10));
+        assert!(v11.is_more_capable_than(&v09));
+        assert!(v10.is_more_capable_than(&v09));
+        assert!(!v10.is_more_capable_than(&v11));
+        assert!(!v09.is_more_capable_than(&v10));
+        assert!(!v11.is_more_capable_than(&v11));
+
+        assert!(!v2.is_more_capable_than(&v11));
+        assert!(!v11.is_more_capable_than(&v2));
+        assert!(!v3.is_more_capable_than(&v2));
+        assert!(!v2.is_more_capable_than(&v3));
+        assert!(!v3.is_more_capable_than(&v09));
+        assert!(!v2.is_more_capable_than(&v2));
+
+        assert!(!HttpVersion::from_parts(4, 2).is_more_capable_than(&v09));
+        assert!(!v11.is_more_capable_than(&HttpVersion::from_parts(1, 2)));
+    }
 }
@@ -0,0 +1,70 @@
+//! Macros for version literals and internal instrumentation.
+
+/// Create an `HttpVersion` constant from a version field literal, checked at compile
+/// time.
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate uhttp_version;
+///
+/// use uhttp_version::HttpVersion;
+///
+/// const VER: HttpVersion = http_version!("HTTP/1.1");
+///
+/// fn main() {
+///     assert_eq!(VER, HttpVersion::HTTP_11);
+///     assert_eq!(http_version!("HTTP/4.2"), HttpVersion::from_parts(4, 2));
+/// }
+/// ```
+///
+/// A malformed literal fails the build:
+///
+/// ```rust,compile_fail
+/// #[macro_use]
+/// extern crate uhttp_version;
+///
+/// fn main() {
+///     let _ = http_version!("HTTP/1.10");
+/// }
+/// ```
+#[macro_export]
+macro_rules! http_version {
+    ($s:expr) => {{
+        const VER: $crate::HttpVersion = match $crate::HttpVersion::from_bytes_const(
+            $s.as_bytes())
+        {
+            Ok(ver) => ver,
+            Err(_) => panic!("malformed HTTP version literal"),
+        };
+
+        VER
+    }};
+}
+
+/// Evaluate to the given parse result, first reporting it with the given `tracing_impl`
+/// function if the `tracing` feature is enabled.
+macro_rules! traced {
+    ($report:ident, $s:expr, $res:expr) => {{
+        let res = $res;
+
+        #[cfg(feature = "tracing")]
+        ::tracing_impl::$report($s, &res);
+
+        res
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use HttpVersion;
+
+    #[test]
+    fn test_http_version() {
+        const V10: HttpVersion = http_version!("HTTP/1.0");
+
+        assert_eq!(V10, HttpVersion::HTTP_10);
+        assert_eq!(http_version!("HTTP/0.9"), HttpVersion::HTTP_09);
+        assert_eq!(http_version!("HTTP/2.0"), HttpVersion::HTTP_2);
+        assert_eq!(http_version!("HTTP/9.9"), HttpVersion::from_parts(9, 9));
+    }
+}
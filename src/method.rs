@@ -0,0 +1,198 @@
+//! Request method parsing and formatting [RFC9110§9].
+
+use core::fmt;
+
+use grammar::is_token;
+
+/// HTTP request method [RFC9110§9].
+///
+/// Method names are case-sensitive, so `get` parses as an extension method rather than
+/// `Get`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum HttpMethod {
+    /// `GET` [RFC9110§9.3.1].
+    Get,
+    /// `HEAD` [RFC9110§9.3.2].
+    Head,
+    /// `POST` [RFC9110§9.3.3].
+    Post,
+    /// `PUT` [RFC9110§9.3.4].
+    Put,
+    /// `DELETE` [RFC9110§9.3.5].
+    Delete,
+    /// `CONNECT` [RFC9110§9.3.6].
+    Connect,
+    /// `OPTIONS` [RFC9110§9.3.7].
+    Options,
+    /// `TRACE` [RFC9110§9.3.8].
+    Trace,
+    /// `PATCH` [RFC5789].
+    Patch,
+    /// Any other method token.
+    Extension(ExtensionMethod),
+}
+
+impl HttpMethod {
+    /// Try to parse an `HttpMethod` from the given method token bytes.
+    pub fn from_bytes(s: &[u8]) -> Result<Self, HttpMethodError> {
+        use self::HttpMethod::*;
+
+        Ok(match s {
+            b"GET" => Get,
+            b"HEAD" => Head,
+            b"POST" => Post,
+            b"PUT" => Put,
+            b"DELETE" => Delete,
+            b"CONNECT" => Connect,
+            b"OPTIONS" => Options,
+            b"TRACE" => Trace,
+            b"PATCH" => Patch,
+            _ => Extension(ExtensionMethod::from_bytes(s)?),
+        })
+    }
+
+    /// Retrieve the method token.
+    pub fn as_str(&self) -> &str {
+        use self::HttpMethod::*;
+
+        match *self {
+            Get => "GET",
+            Head => "HEAD",
+            Post => "POST",
+            Put => "PUT",
+            Delete => "DELETE",
+            Connect => "CONNECT",
+            Options => "OPTIONS",
+            Trace => "TRACE",
+            Patch => "PATCH",
+            Extension(ref ext) => ext.as_str(),
+        }
+    }
+}
+
+impl fmt::Display for HttpMethod {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+impl core::str::FromStr for HttpMethod {
+    type Err = HttpMethodError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HttpMethod::from_bytes(s.as_bytes())
+    }
+}
+
+/// Unregistered method token, stored inline so `HttpMethod` stays `Copy` and needs no
+/// allocation.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct ExtensionMethod {
+    /// Token bytes, zero-filled past `len`.
+    buf: [u8; ExtensionMethod::MAX_LEN],
+    /// Number of token bytes in `buf`.
+    len: u8,
+}
+
+impl ExtensionMethod {
+    /// Maximum supported length of an extension method token.
+    pub const MAX_LEN: usize = 32;
+
+    /// Try to create an `ExtensionMethod` from the given method token bytes.
+    pub fn from_bytes(s: &[u8]) -> Result<Self, HttpMethodError> {
+        if !is_token(s) {
+            return Err(HttpMethodError::BadToken);
+        }
+
+        if s.len() > Self::MAX_LEN {
+            return Err(HttpMethodError::TooLong);
+        }
+
+        let mut buf = [0; Self::MAX_LEN];
+        buf[..s.len()].copy_from_slice(s);
+
+        Ok(ExtensionMethod { buf, len: s.len() as u8 })
+    }
+
+    /// Retrieve the method token.
+    pub fn as_str(&self) -> &str {
+        // Token characters are all ASCII, so this can't fail.
+        core::str::from_utf8(&self.buf[..self.len as usize]).unwrap_or("")
+    }
+}
+
+/// Errors that can occur when parsing an `HttpMethod`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HttpMethodError {
+    /// The method was empty or contained a non-token character.
+    BadToken,
+    /// The extension method was longer than `ExtensionMethod::MAX_LEN`.
+    TooLong,
+}
+
+impl fmt::Display for HttpMethodError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(match *self {
+            HttpMethodError::BadToken => "HTTP method isn't a valid token",
+            HttpMethodError::TooLong => "HTTP extension method is too long",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HttpMethodError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_method() {
+        assert_eq!(HttpMethod::from_bytes(b"GET"), Ok(HttpMethod::Get));
+        assert_eq!(HttpMethod::from_bytes(b"HEAD"), Ok(HttpMethod::Head));
+        assert_eq!(HttpMethod::from_bytes(b"POST"), Ok(HttpMethod::Post));
+        assert_eq!(HttpMethod::from_bytes(b"PUT"), Ok(HttpMethod::Put));
+        assert_eq!(HttpMethod::from_bytes(b"DELETE"), Ok(HttpMethod::Delete));
+        assert_eq!(HttpMethod::from_bytes(b"CONNECT"), Ok(HttpMethod::Connect));
+        assert_eq!(HttpMethod::from_bytes(b"OPTIONS"), Ok(HttpMethod::Options));
+        assert_eq!(HttpMethod::from_bytes(b"TRACE"), Ok(HttpMethod::Trace));
+        assert_eq!(HttpMethod::from_bytes(b"PATCH"), Ok(HttpMethod::Patch));
+        assert_eq!("GET".parse(), Ok(HttpMethod::Get));
+
+        let ext = HttpMethod::from_bytes(b"PROPFIND").unwrap();
+        assert_eq!(ext, HttpMethod::Extension(ExtensionMethod::from_bytes(b"PROPFIND").unwrap()));
+        assert_eq!(ext.as_str(), "PROPFIND");
+        assert_ne!(ext, HttpMethod::from_bytes(b"MKCOL").unwrap());
+
+        let ext = HttpMethod::from_bytes(b"get").unwrap();
+        assert_ne!(ext, HttpMethod::Get);
+        assert_eq!(ext.as_str(), "get");
+        assert_eq!(HttpMethod::from_bytes(b"X-My_Method!").unwrap().as_str(), "X-My_Method!");
+
+        let long = [b'A'; ExtensionMethod::MAX_LEN];
+        assert_eq!(HttpMethod::from_bytes(&long).unwrap().as_str().len(),
+                   ExtensionMethod::MAX_LEN);
+        assert_eq!(HttpMethod::from_bytes(&[b'A'; ExtensionMethod::MAX_LEN + 1]),
+                   Err(HttpMethodError::TooLong));
+
+        assert_eq!(HttpMethod::from_bytes(b""), Err(HttpMethodError::BadToken));
+        assert_eq!(HttpMethod::from_bytes(b"GET "), Err(HttpMethodError::BadToken));
+        assert_eq!(HttpMethod::from_bytes(b"G(T"), Err(HttpMethodError::BadToken));
+        assert_eq!(HttpMethod::from_bytes(b"GE\x80"), Err(HttpMethodError::BadToken));
+        assert_eq!("".parse::<HttpMethod>(), Err(HttpMethodError::BadToken));
+    }
+
+    #[test]
+    fn test_method_display() {
+        assert_eq!(HttpMethod::Get.to_string(), "GET");
+        assert_eq!(HttpMethod::Options.to_string(), "OPTIONS");
+        assert_eq!(HttpMethod::Patch.to_string(), "PATCH");
+        assert_eq!("PROPFIND".parse::<HttpMethod>().unwrap().to_string(), "PROPFIND");
+
+        for &name in &["GET", "HEAD", "POST", "PUT", "DELETE", "CONNECT", "OPTIONS", "TRACE",
+                       "PATCH", "LINK"] {
+            assert_eq!(name.parse::<HttpMethod>().unwrap().to_string(), name);
+        }
+    }
+}
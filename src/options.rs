@@ -0,0 +1,227 @@
+//! Configurable parsing of malformed but common version fields.
+
+use grammar::{to_digit, trim_ows};
+use {HttpVersion, HttpVersionError, parse_number};
+
+/// Options for relaxing the strict version field syntax used by `from_bytes`.
+///
+/// Each option tolerates a common deviation seen from real-world clients. The default
+/// options enable none of them, making `parse` equivalent to `from_bytes`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Default)]
+pub struct ParseOptions {
+    /// Accept the `HTTP` name in any letter case, such as `http/1.1`.
+    pub ignore_case: bool,
+    /// Accept and ignore spaces and tabs surrounding the field.
+    pub trim_whitespace: bool,
+    /// Accept a bare `\n` line terminator in `parse_line`, in addition to `\r\n`.
+    pub bare_lf: bool,
+    /// Accept version parts of more than one digit, as allowed by the obsolete
+    /// [RFC2616§3.1] grammar, such as `HTTP/1.12` or `HTTP/01.1`.
+    ///
+    /// Leading zeros are ignored, and parts above 255 fail with
+    /// `HttpVersionError::OutOfRange`. The parsed version may not be `is_valid`.
+    pub multi_digit: bool,
+}
+
+impl ParseOptions {
+    /// Options following the strict syntax [RFC9112§2.3].
+    pub const STRICT: ParseOptions = ParseOptions {
+        ignore_case: false,
+        trim_whitespace: false,
+        bare_lf: false,
+        multi_digit: false,
+    };
+
+    /// Options tolerating every supported deviation that still produces a version with
+    /// single-digit parts.
+    pub const LENIENT: ParseOptions = ParseOptions {
+        ignore_case: true,
+        trim_whitespace: true,
+        bare_lf: true,
+        multi_digit: false,
+    };
+
+    /// Try to parse an `HttpVersion` from the given bytes, relaxing the syntax as
+    /// configured.
+    pub fn parse(&self, s: &[u8]) -> Result<HttpVersion, HttpVersionError> {
+        // Name as it appears in a well-formed field.
+        const NAME: &[u8] = b"HTTP/";
+
+        let s = if self.trim_whitespace { trim_ows(s) } else { s };
+
+        if !self.ignore_case && !self.multi_digit {
+            return HttpVersion::from_bytes(s);
+        }
+
+        let ver = match (s.get(..NAME.len()), s.get(NAME.len()..)) {
+            (Some(name), Some(ver)) if name == NAME => ver,
+            (Some(name), Some(ver)) if self.ignore_case && name.eq_ignore_ascii_case(NAME) => {
+                ver
+            },
+            _ => return Err(HttpVersionError::BadPrefix),
+        };
+
+        if self.multi_digit {
+            parse_multi_digit(ver)
+        } else {
+            parse_number(ver)
+        }
+    }
+
+    /// Try to parse an `HttpVersion` from a line ending in the version field, stripping
+    /// an optional trailing line terminator before parsing as in `parse`.
+    ///
+    /// On success, returns the version along with whether a terminator was present.
+    pub fn parse_line(&self, s: &[u8]) -> Result<(HttpVersion, bool), HttpVersionError> {
+        let field = s.strip_suffix(b"\r\n")
+            .or_else(|| if self.bare_lf { s.strip_suffix(b"\n") } else { None })
+            .unwrap_or(s);
+
+        self.parse(field).map(|ver| (ver, field.len() != s.len()))
+    }
+}
+
+/// Parse the `1*DIGIT "." 1*DIGIT` version number following the name [RFC2616§3.1].
+fn parse_multi_digit(ver: &[u8]) -> Result<HttpVersion, HttpVersionError> {
+    let mut parts = ver.splitn(2, |&b| b == b'.');
+
+    let (major, minor) = match (parts.next(), parts.next()) {
+        (Some(major), Some(minor)) => (major, minor),
+        _ => return Err(HttpVersionError::BadSeparator),
+    };
+
+    Ok(HttpVersion::from_parts(parse_part(major)?, parse_part(minor)?))
+}
+
+/// Parse a nonempty run of decimal digits as a version part.
+fn parse_part(s: &[u8]) -> Result<u8, HttpVersionError> {
+    if s.is_empty() {
+        return Err(HttpVersionError::BadDigit);
+    }
+
+    s.iter().try_fold(0u8, |part, &b| {
+        let digit = to_digit(b).ok_or(HttpVersionError::BadDigit)?;
+
+        part.checked_mul(10)
+            .and_then(|part| part.checked_add(digit))
+            .ok_or(HttpVersionError::OutOfRange)
+    })
+}
+
+impl HttpVersion {
+    /// Try to parse an `HttpVersion` from the given bytes, tolerating a case-insensitive
+    /// name and surrounding whitespace.
+    ///
+    /// This is equivalent to parsing with `ParseOptions::LENIENT`.
+    pub fn from_bytes_lenient(s: &[u8]) -> Result<Self, HttpVersionError> {
+        ParseOptions::LENIENT.parse(s)
+    }
+
+    /// Try to parse an `HttpVersion` from the given bytes, accepting the multi-digit
+    /// version parts allowed by the obsolete [RFC2616§3.1] grammar, such as `HTTP/1.12`.
+    ///
+    /// This is equivalent to parsing with `ParseOptions::multi_digit` as the only option.
+    pub fn from_bytes_rfc2616(s: &[u8]) -> Result<Self, HttpVersionError> {
+        ParseOptions { multi_digit: true, ..ParseOptions::STRICT }.parse(s)
+    }
+
+    /// Try to parse an `HttpVersion` from a line ending in the version field, such as
+    /// one read up to and including its `\r\n` terminator.
+    ///
+    /// On success, returns the version along with whether the terminator was present. A
+    /// bare `\n` terminator is rejected unless opted into with `ParseOptions::bare_lf`.
+    pub fn from_line(s: &[u8]) -> Result<(Self, bool), HttpVersionError> {
+        ParseOptions::STRICT.parse_line(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lenient() {
+        assert_eq!(HttpVersion::from_bytes_lenient(b"HTTP/1.1"), Ok(HttpVersion::HTTP_11));
+        assert_eq!(HttpVersion::from_bytes_lenient(b"http/1.1"), Ok(HttpVersion::HTTP_11));
+        assert_eq!(HttpVersion::from_bytes_lenient(b"Http/1.0"), Ok(HttpVersion::HTTP_10));
+        assert_eq!(HttpVersion::from_bytes_lenient(b"HTTP/1.1 "), Ok(HttpVersion::HTTP_11));
+        assert_eq!(HttpVersion::from_bytes_lenient(b" \thttp/1.1\t "), Ok(HttpVersion::HTTP_11));
+
+        assert_eq!(HttpVersion::from_bytes_lenient(b"HTTP /1.1"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_bytes_lenient(b"HTTP/1. 1"),
+                   Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_bytes_lenient(b"HTTP/1.1\r\n"),
+                   Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_bytes_lenient(b"htt"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_bytes_lenient(b"   "), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_bytes_lenient(b""), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_bytes_lenient(b"http/1-1"),
+                   Err(HttpVersionError::BadSeparator));
+    }
+
+    #[test]
+    fn test_from_line() {
+        let v11 = HttpVersion::HTTP_11;
+
+        assert_eq!(HttpVersion::from_line(b"HTTP/1.1\r\n"), Ok((v11, true)));
+        assert_eq!(HttpVersion::from_line(b"HTTP/1.1"), Ok((v11, false)));
+        assert_eq!(HttpVersion::from_line(b"HTTP/1.1\n"), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_line(b"HTTP/1.1\r"), Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_line(b"HTTP/1.1\r\n\r\n"),
+                   Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_line(b"http/1.1\r\n"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_line(b"\r\n"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_line(b""), Err(HttpVersionError::BadPrefix));
+    }
+
+    #[test]
+    fn test_rfc2616() {
+        let parse = HttpVersion::from_bytes_rfc2616;
+
+        assert_eq!(parse(b"HTTP/1.1"), Ok(HttpVersion::HTTP_11));
+        assert_eq!(parse(b"HTTP/1.12"), Ok(HttpVersion { major: 1, minor: 12 }));
+        assert_eq!(parse(b"HTTP/01.1"), Ok(HttpVersion::HTTP_11));
+        assert_eq!(parse(b"HTTP/001.0000"), Ok(HttpVersion::HTTP_10));
+        assert_eq!(parse(b"HTTP/12.345"), Err(HttpVersionError::OutOfRange));
+        assert_eq!(parse(b"HTTP/255.255"), Ok(HttpVersion { major: 255, minor: 255 }));
+        assert_eq!(parse(b"HTTP/256.0"), Err(HttpVersionError::OutOfRange));
+        assert_eq!(parse(b"HTTP/0000000000001.1"), Ok(HttpVersion::HTTP_11));
+
+        assert_eq!(parse(b"HTTP/1"), Err(HttpVersionError::BadSeparator));
+        assert_eq!(parse(b"HTTP/"), Err(HttpVersionError::BadSeparator));
+        assert_eq!(parse(b"HTTP/.1"), Err(HttpVersionError::BadDigit));
+        assert_eq!(parse(b"HTTP/1."), Err(HttpVersionError::BadDigit));
+        assert_eq!(parse(b"HTTP/1.1.1"), Err(HttpVersionError::BadDigit));
+        assert_eq!(parse(b"HTTP/+1.1"), Err(HttpVersionError::BadDigit));
+        assert_eq!(parse(b"http/1.12"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(parse(b"HTTP"), Err(HttpVersionError::BadPrefix));
+
+        let lenient = ParseOptions { multi_digit: true, ..ParseOptions::LENIENT };
+        assert_eq!(lenient.parse(b" http/1.10 "), Ok(HttpVersion { major: 1, minor: 10 }));
+        assert_eq!(ParseOptions::LENIENT.parse(b"HTTP/1.10"), Err(HttpVersionError::BadLength));
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let case = ParseOptions { ignore_case: true, ..ParseOptions::STRICT };
+        assert_eq!(case.parse(b"hTtP/1.1"), Ok(HttpVersion::HTTP_11));
+        assert_eq!(case.parse(b"http/1.1 "), Err(HttpVersionError::BadLength));
+
+        let trim = ParseOptions { trim_whitespace: true, ..ParseOptions::STRICT };
+        assert_eq!(trim.parse(b" HTTP/1.1 "), Ok(HttpVersion::HTTP_11));
+        assert_eq!(trim.parse(b" http/1.1 "), Err(HttpVersionError::BadPrefix));
+
+        let lf = ParseOptions { bare_lf: true, ..ParseOptions::STRICT };
+        assert_eq!(lf.parse_line(b"HTTP/1.1\n"), Ok((HttpVersion::HTTP_11, true)));
+        assert_eq!(lf.parse_line(b"HTTP/1.1\r\n"), Ok((HttpVersion::HTTP_11, true)));
+        assert_eq!(lf.parse(b"HTTP/1.1\n"), Err(HttpVersionError::BadLength));
+        assert_eq!(ParseOptions::LENIENT.parse_line(b" http/1.0 \n"),
+                   Ok((HttpVersion::HTTP_10, true)));
+
+        assert_eq!(ParseOptions::default(), ParseOptions::STRICT);
+
+        for s in &[&b"HTTP/1.1"[..], b"http/1.1", b"HTTP/1.1 ", b"HTTP/1-1", b"", b"HTTP/"] {
+            assert_eq!(ParseOptions::STRICT.parse(s), HttpVersion::from_bytes(s));
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! HTTP/2 connection preface detection [RFC9113§3.4].
+
+use HttpVersion;
+
+/// Client connection preface that begins every HTTP/2 connection [RFC9113§3.4].
+pub const H2_PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+
+/// Result of checking a buffer for the HTTP/2 connection preface.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum PrefaceMatch {
+    /// The buffer begins with the complete preface.
+    Complete {
+        /// Version the preface belongs to.
+        version: HttpVersion,
+        /// Length of the preface at the start of the buffer.
+        len: usize,
+    },
+    /// The buffer is a proper prefix of the preface, so more bytes are needed to decide.
+    Partial,
+    /// The buffer doesn't begin with the preface.
+    Mismatch,
+}
+
+impl HttpVersion {
+    /// Check if the given buffer begins with the HTTP/2 client connection preface, such as
+    /// sent over cleartext connections using prior knowledge.
+    ///
+    /// Because the preface is shaped like a request line, this should be checked before
+    /// parsing an HTTP/1.x request.
+    pub fn detect_preface(s: &[u8]) -> PrefaceMatch {
+        if s.starts_with(H2_PREFACE) {
+            PrefaceMatch::Complete {
+                version: HttpVersion::HTTP_2,
+                len: H2_PREFACE.len(),
+            }
+        } else if H2_PREFACE.starts_with(s) {
+            PrefaceMatch::Partial
+        } else {
+            PrefaceMatch::Mismatch
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_detect_preface() {
+        let complete = PrefaceMatch::Complete { version: HttpVersion::HTTP_2, len: 24 };
+
+        assert_eq!(HttpVersion::detect_preface(b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n"), complete);
+        assert_eq!(HttpVersion::detect_preface(b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n\x00\x00\x12"),
+                   complete);
+
+        for len in 0..H2_PREFACE.len() {
+            assert_eq!(HttpVersion::detect_preface(&H2_PREFACE[..len]), PrefaceMatch::Partial);
+        }
+
+        assert_eq!(HttpVersion::detect_preface(b"GET / HTTP/1.1\r\n"), PrefaceMatch::Mismatch);
+        assert_eq!(HttpVersion::detect_preface(b"PRI / HTTP/2.0\r\n"), PrefaceMatch::Mismatch);
+        assert_eq!(HttpVersion::detect_preface(b"PRI * HTTP/1.1\r\n\r\nSM\r\n\r\n"),
+                   PrefaceMatch::Mismatch);
+        assert_eq!(HttpVersion::detect_preface(b"PRI * HTTP/2.0\r\n\r\nSM\r\n\n"),
+                   PrefaceMatch::Mismatch);
+        assert_eq!(HttpVersion::detect_preface(b"pri"), PrefaceMatch::Mismatch);
+    }
+}
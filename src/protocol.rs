@@ -0,0 +1,529 @@
+//! Protocol tokens in header fields, such as `Upgrade` [RFC9110§7.8], `Via`
+//! [RFC9110§7.6.3], and `Alt-Svc` [RFC7838§3].
+
+use core::fmt;
+
+use grammar::{is_tchar, is_token, trim_ows};
+use {HttpVersion, parse_number};
+
+/// Version of a protocol named in a header field.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum VersionToken<'a> {
+    /// Version of the HTTP protocol.
+    Http(HttpVersion),
+    /// Version of some other protocol, or an HTTP version that isn't `DIGIT "." DIGIT`.
+    Other(&'a [u8]),
+}
+
+impl<'a> VersionToken<'a> {
+    /// Interpret the given version of the protocol with the given name.
+    ///
+    /// The version is only parsed as an `HttpVersion` if the protocol name is `HTTP`,
+    /// compared case-insensitively.
+    fn new(name: &[u8], version: &'a [u8]) -> Self {
+        if !name.eq_ignore_ascii_case(b"HTTP") {
+            return VersionToken::Other(version);
+        }
+
+        match parse_number(version) {
+            Ok(ver) => VersionToken::Http(ver),
+            Err(_) => VersionToken::Other(version),
+        }
+    }
+}
+
+/// Writes the version as it appears after the protocol name.
+impl<'a> fmt::Display for VersionToken<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VersionToken::Http(ver) => write!(fmt, "{:#}", ver),
+            VersionToken::Other(s) => write_token(fmt, s),
+        }
+    }
+}
+
+/// Protocol offered in an `Upgrade` header field [RFC9110§7.8], such as `h2c` or
+/// `HTTP/2.0`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct UpgradeProtocol<'a> {
+    /// Protocol name token.
+    pub name: &'a [u8],
+    /// Protocol version, if given.
+    pub version: Option<VersionToken<'a>>,
+}
+
+impl<'a> UpgradeProtocol<'a> {
+    /// Try to parse a single `protocol-name ["/" protocol-version]` element.
+    pub fn from_bytes(s: &'a [u8]) -> Result<Self, ProtocolError> {
+        let (name, version) = match s.iter().position(|&b| b == b'/') {
+            Some(idx) => (&s[..idx], Some(&s[idx + 1..])),
+            None => (s, None),
+        };
+
+        if !is_token(name) {
+            return Err(ProtocolError::BadName);
+        }
+
+        let version = match version {
+            Some(version) if is_token(version) => Some(VersionToken::new(name, version)),
+            Some(_) => return Err(ProtocolError::BadVersion),
+            None => None,
+        };
+
+        Ok(UpgradeProtocol { name, version })
+    }
+
+    /// Create an iterator over the protocols in the given `Upgrade` field value, in the
+    /// order of preference given by the client.
+    pub fn parse_list(s: &'a [u8]) -> UpgradeProtocols<'a> {
+        UpgradeProtocols { rest: Some(s) }
+    }
+}
+
+/// Iterator over the comma-separated protocols of an `Upgrade` field value, skipping
+/// empty list elements [RFC9110§5.6.1].
+#[derive(Clone, Debug)]
+pub struct UpgradeProtocols<'a> {
+    /// Remaining field value, or `None` once exhausted.
+    rest: Option<&'a [u8]>,
+}
+
+impl<'a> Iterator for UpgradeProtocols<'a> {
+    type Item = Result<UpgradeProtocol<'a>, ProtocolError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let s = self.rest?;
+
+            let elem = match s.iter().position(|&b| b == b',') {
+                Some(idx) => {
+                    self.rest = Some(&s[idx + 1..]);
+                    &s[..idx]
+                }
+                None => {
+                    self.rest = None;
+                    s
+                }
+            };
+
+            let elem = trim_ows(elem);
+
+            if !elem.is_empty() {
+                return Some(UpgradeProtocol::from_bytes(elem));
+            }
+        }
+    }
+}
+
+/// Protocol through which a `Via` header field entry [RFC9110§7.6.3] was received, such
+/// as `1.1` or `HTTP/1.0`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct ReceivedProtocol<'a> {
+    /// Protocol name token, which is omitted for HTTP.
+    pub name: Option<&'a [u8]>,
+    /// Protocol version.
+    pub version: VersionToken<'a>,
+}
+
+impl<'a> ReceivedProtocol<'a> {
+    /// Try to parse a `[protocol-name "/"] protocol-version` received protocol.
+    ///
+    /// A missing name means the protocol is HTTP, so the version is parsed as an
+    /// `HttpVersion` when possible.
+    pub fn from_bytes(s: &'a [u8]) -> Result<Self, ProtocolError> {
+        let (name, version) = match s.iter().position(|&b| b == b'/') {
+            Some(idx) => (Some(&s[..idx]), &s[idx + 1..]),
+            None => (None, s),
+        };
+
+        match name {
+            Some(name) if !is_token(name) => return Err(ProtocolError::BadName),
+            _ => {}
+        }
+
+        if !is_token(version) {
+            return Err(ProtocolError::BadVersion);
+        }
+
+        Ok(ReceivedProtocol {
+            name,
+            version: VersionToken::new(name.unwrap_or(b"HTTP"), version),
+        })
+    }
+
+    /// Try to parse the received protocol at the start of the given `Via` list element,
+    /// returning it along with the rest of the element, starting at the `received-by`
+    /// field.
+    pub fn from_entry(s: &'a [u8]) -> Result<(Self, &'a [u8]), ProtocolError> {
+        let s = trim_ows(s);
+
+        let (proto, rest) = match s.iter().position(|&b| b == b' ' || b == b'\t') {
+            Some(idx) => (&s[..idx], trim_ows(&s[idx..])),
+            None => (s, &[][..]),
+        };
+
+        ReceivedProtocol::from_bytes(proto).map(|proto| (proto, rest))
+    }
+}
+
+/// Writes the received protocol in the form used in `Via` entries.
+impl<'a> fmt::Display for ReceivedProtocol<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(name) = self.name {
+            write_token(fmt, name)?;
+            fmt.write_str("/")?;
+        }
+
+        write!(fmt, "{}", self.version)
+    }
+}
+
+/// Protocol named by an `Alt-Svc` `protocol-id` [RFC7838§3], which is the
+/// percent-encoded ALPN protocol ID of the alternative service, such as `h3` or `h3-29`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum AltSvcProtocol<'a> {
+    /// Final version of HTTP, identified by its ALPN protocol ID.
+    Http(HttpVersion),
+    /// Draft version of HTTP identified by a suffixed ALPN protocol ID, such as `h3-29`.
+    Draft {
+        /// Version the draft is for.
+        version: HttpVersion,
+        /// Draft suffix following the `-`, such as `29`.
+        draft: &'a [u8],
+    },
+    /// Some other protocol, with its ID still percent-encoded.
+    Other(&'a [u8]),
+}
+
+impl<'a> AltSvcProtocol<'a> {
+    /// Try to parse the given `protocol-id` token.
+    ///
+    /// Percent-encoded octets are decoded before looking up the ALPN protocol ID, so
+    /// `http%2F1.1` identifies HTTP/1.1.
+    ///
+    /// Only the ID each version negotiates over TLS identifies it, so the cleartext
+    /// `h2c` is kept as `Other` rather than written back as `h2`.
+    pub fn from_bytes(s: &'a [u8]) -> Result<Self, ProtocolError> {
+        // Long enough for the longest ALPN protocol ID of a known version.
+        let mut buf = [0; 8];
+
+        if !is_token(s) {
+            return Err(ProtocolError::BadName);
+        }
+
+        match percent_decode(s, &mut buf) {
+            Ok(id) => match HttpVersion::from_alpn(id) {
+                Some(ver) if ver.as_alpn() == Some(id) => return Ok(AltSvcProtocol::Http(ver)),
+                _ => {},
+            },
+            Err(DecodeError::Malformed) => return Err(ProtocolError::BadName),
+            // Too long to be a known ID, but may still be a draft.
+            Err(DecodeError::TooLong) => {},
+        }
+
+        let version = match s.get(..3) {
+            Some(b"h2-") => HttpVersion::HTTP_2,
+            Some(b"h3-") => HttpVersion::HTTP_3,
+            _ => return Ok(AltSvcProtocol::Other(s)),
+        };
+
+        match &s[3..] {
+            b"" => Ok(AltSvcProtocol::Other(s)),
+            draft => Ok(AltSvcProtocol::Draft { version, draft }),
+        }
+    }
+
+    /// Retrieve the version of HTTP the protocol is for, whether final or a draft.
+    pub fn version(&self) -> Option<HttpVersion> {
+        match *self {
+            AltSvcProtocol::Http(ver) | AltSvcProtocol::Draft { version: ver, .. } => Some(ver),
+            AltSvcProtocol::Other(_) => None,
+        }
+    }
+}
+
+/// Writes the percent-encoded `protocol-id` token.
+impl<'a> fmt::Display for AltSvcProtocol<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AltSvcProtocol::Http(ver) => match ver.as_alpn() {
+                Some(id) => id.iter().try_for_each(|&b| {
+                    if is_tchar(b) && b != b'%' {
+                        fmt::Write::write_char(fmt, b as char)
+                    } else {
+                        write!(fmt, "%{:02X}", b)
+                    }
+                }),
+                // Versions without an ALPN ID are written in the same `http/x.y` form as
+                // HTTP/1.x, which parses back as `Other`.
+                None => write!(fmt, "http%2F{}.{}", ver.major, ver.minor),
+            },
+            AltSvcProtocol::Draft { version, draft } => {
+                write!(fmt, "h{}-", version.major)?;
+                write_token(fmt, draft)
+            },
+            AltSvcProtocol::Other(s) => write_token(fmt, s),
+        }
+    }
+}
+
+/// Reasons percent-decoding can fail.
+enum DecodeError {
+    /// A `%` wasn't followed by two hex digits.
+    Malformed,
+    /// The decoded bytes didn't fit the buffer.
+    TooLong,
+}
+
+/// Decode the percent-encoded octets of the given bytes into the given buffer, returning
+/// the decoded bytes.
+fn percent_decode<'b>(s: &[u8], buf: &'b mut [u8]) -> Result<&'b [u8], DecodeError> {
+    let mut len = 0;
+    let mut iter = s.iter();
+
+    while let Some(&b) = iter.next() {
+        let b = if b == b'%' {
+            let hi = iter.next().and_then(|&b| hex_digit(b));
+            let lo = iter.next().and_then(|&b| hex_digit(b));
+
+            match (hi, lo) {
+                (Some(hi), Some(lo)) => hi << 4 | lo,
+                _ => return Err(DecodeError::Malformed),
+            }
+        } else {
+            b
+        };
+
+        *buf.get_mut(len).ok_or(DecodeError::TooLong)? = b;
+        len += 1;
+    }
+
+    Ok(&buf[..len])
+}
+
+/// Convert the given ASCII hex digit to its numeric value.
+fn hex_digit(b: u8) -> Option<u8> {
+    (b as char).to_digit(16).map(|d| d as u8)
+}
+
+/// Write the given token bytes, which are ASCII if the token is well-formed.
+fn write_token(fmt: &mut fmt::Formatter, s: &[u8]) -> fmt::Result {
+    s.iter().try_for_each(|&b| fmt::Write::write_char(fmt, b as char))
+}
+
+/// Errors that can occur when parsing a protocol in a header field.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ProtocolError {
+    /// The protocol name was empty or contained a non-token character.
+    BadName,
+    /// The protocol version was empty or contained a non-token character.
+    BadVersion,
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(match *self {
+            ProtocolError::BadName => "protocol name isn't a valid token",
+            ProtocolError::BadVersion => "protocol version isn't a valid token",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ProtocolError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_upgrade_protocol() {
+        assert_eq!(UpgradeProtocol::from_bytes(b"h2c"), Ok(UpgradeProtocol {
+            name: b"h2c",
+            version: None,
+        }));
+
+        assert_eq!(UpgradeProtocol::from_bytes(b"HTTP/2.0"), Ok(UpgradeProtocol {
+            name: b"HTTP",
+            version: Some(VersionToken::Http(HttpVersion::HTTP_2)),
+        }));
+
+        assert_eq!(UpgradeProtocol::from_bytes(b"http/1.1"), Ok(UpgradeProtocol {
+            name: b"http",
+            version: Some(VersionToken::Http(HttpVersion::HTTP_11)),
+        }));
+
+        assert_eq!(UpgradeProtocol::from_bytes(b"HTTP/2"), Ok(UpgradeProtocol {
+            name: b"HTTP",
+            version: Some(VersionToken::Other(b"2")),
+        }));
+
+        assert_eq!(UpgradeProtocol::from_bytes(b"RTA/x11"), Ok(UpgradeProtocol {
+            name: b"RTA",
+            version: Some(VersionToken::Other(b"x11")),
+        }));
+
+        assert_eq!(UpgradeProtocol::from_bytes(b"SHTTP/1.3"), Ok(UpgradeProtocol {
+            name: b"SHTTP",
+            version: Some(VersionToken::Other(b"1.3")),
+        }));
+
+        assert_eq!(UpgradeProtocol::from_bytes(b""), Err(ProtocolError::BadName));
+        assert_eq!(UpgradeProtocol::from_bytes(b"/1.1"), Err(ProtocolError::BadName));
+        assert_eq!(UpgradeProtocol::from_bytes(b"web socket"), Err(ProtocolError::BadName));
+        assert_eq!(UpgradeProtocol::from_bytes(b"HTTP/"), Err(ProtocolError::BadVersion));
+        assert_eq!(UpgradeProtocol::from_bytes(b"HTTP/1/1"), Err(ProtocolError::BadVersion));
+    }
+
+    #[test]
+    fn test_received_protocol() {
+        assert_eq!(ReceivedProtocol::from_bytes(b"1.1"), Ok(ReceivedProtocol {
+            name: None,
+            version: VersionToken::Http(HttpVersion::HTTP_11),
+        }));
+
+        assert_eq!(ReceivedProtocol::from_bytes(b"HTTP/1.0"), Ok(ReceivedProtocol {
+            name: Some(b"HTTP"),
+            version: VersionToken::Http(HttpVersion::HTTP_10),
+        }));
+
+        assert_eq!(ReceivedProtocol::from_bytes(b"2"), Ok(ReceivedProtocol {
+            name: None,
+            version: VersionToken::Other(b"2"),
+        }));
+
+        assert_eq!(ReceivedProtocol::from_bytes(b"RTSP/1.0"), Ok(ReceivedProtocol {
+            name: Some(b"RTSP"),
+            version: VersionToken::Other(b"1.0"),
+        }));
+
+        assert_eq!(ReceivedProtocol::from_bytes(b""), Err(ProtocolError::BadVersion));
+        assert_eq!(ReceivedProtocol::from_bytes(b"HTTP/"), Err(ProtocolError::BadVersion));
+        assert_eq!(ReceivedProtocol::from_bytes(b"/1.1"), Err(ProtocolError::BadName));
+        assert_eq!(ReceivedProtocol::from_bytes(b"1.1 gw"), Err(ProtocolError::BadVersion));
+
+        let (proto, rest) = ReceivedProtocol::from_entry(b" 1.1 cache-a ").unwrap();
+        assert_eq!(proto.version, VersionToken::Http(HttpVersion::HTTP_11));
+        assert_eq!(rest, b"cache-a");
+
+        let (proto, rest) = ReceivedProtocol::from_entry(b"HTTP/1.0 gw:80 (Proxy 1.0)").unwrap();
+        assert_eq!(proto.name, Some(&b"HTTP"[..]));
+        assert_eq!(proto.version, VersionToken::Http(HttpVersion::HTTP_10));
+        assert_eq!(rest, b"gw:80 (Proxy 1.0)");
+
+        let (proto, rest) = ReceivedProtocol::from_entry(b"1.1").unwrap();
+        assert_eq!(proto.version, VersionToken::Http(HttpVersion::HTTP_11));
+        assert_eq!(rest, b"");
+
+        let entries: Vec<_> = b"1.1 cache-a, HTTP/1.0 gw".split(|&b| b == b',')
+            .map(|e| ReceivedProtocol::from_entry(e).unwrap().0.version)
+            .collect();
+        assert_eq!(entries, [VersionToken::Http(HttpVersion::HTTP_11),
+                             VersionToken::Http(HttpVersion::HTTP_10)]);
+    }
+
+    #[test]
+    fn test_received_protocol_display() {
+        for &s in &["1.1", "HTTP/1.0", "RTSP/1.0", "x", "FOO/bar"] {
+            assert_eq!(ReceivedProtocol::from_bytes(s.as_bytes()).unwrap().to_string(), s);
+        }
+
+        let proto = ReceivedProtocol {
+            name: None,
+            version: VersionToken::Http(HttpVersion::HTTP_11),
+        };
+        assert_eq!(format!("{} proxy", proto), "1.1 proxy");
+    }
+
+    #[test]
+    fn test_alt_svc_protocol() {
+        assert_eq!(AltSvcProtocol::from_bytes(b"h2"),
+                   Ok(AltSvcProtocol::Http(HttpVersion::HTTP_2)));
+        assert_eq!(AltSvcProtocol::from_bytes(b"h3"),
+                   Ok(AltSvcProtocol::Http(HttpVersion::HTTP_3)));
+        assert_eq!(AltSvcProtocol::from_bytes(b"http%2F1.1"),
+                   Ok(AltSvcProtocol::Http(HttpVersion::HTTP_11)));
+        assert_eq!(AltSvcProtocol::from_bytes(b"http%2f1.0"),
+                   Ok(AltSvcProtocol::Http(HttpVersion::HTTP_10)));
+        assert_eq!(AltSvcProtocol::from_bytes(b"h%33"),
+                   Ok(AltSvcProtocol::Http(HttpVersion::HTTP_3)));
+
+        assert_eq!(AltSvcProtocol::from_bytes(b"h3-29"), Ok(AltSvcProtocol::Draft {
+            version: HttpVersion::HTTP_3,
+            draft: b"29",
+        }));
+        assert_eq!(AltSvcProtocol::from_bytes(b"h2-14"), Ok(AltSvcProtocol::Draft {
+            version: HttpVersion::HTTP_2,
+            draft: b"14",
+        }));
+        assert_eq!(AltSvcProtocol::from_bytes(b"h3-Q050-extra-long"), Ok(AltSvcProtocol::Draft {
+            version: HttpVersion::HTTP_3,
+            draft: b"Q050-extra-long",
+        }));
+
+        assert_eq!(AltSvcProtocol::from_bytes(b"h2c"), Ok(AltSvcProtocol::Other(b"h2c")));
+        assert_eq!(AltSvcProtocol::from_bytes(b"h2%63"), Ok(AltSvcProtocol::Other(b"h2%63")));
+        assert_eq!(AltSvcProtocol::from_bytes(b"h3-"), Ok(AltSvcProtocol::Other(b"h3-")));
+        assert_eq!(AltSvcProtocol::from_bytes(b"hq-29"), Ok(AltSvcProtocol::Other(b"hq-29")));
+        assert_eq!(AltSvcProtocol::from_bytes(b"w%3Dx%25y"),
+                   Ok(AltSvcProtocol::Other(b"w%3Dx%25y")));
+        assert_eq!(AltSvcProtocol::from_bytes(b"spdy%2F3.1-and-more"),
+                   Ok(AltSvcProtocol::Other(b"spdy%2F3.1-and-more")));
+
+        assert_eq!(AltSvcProtocol::from_bytes(b""), Err(ProtocolError::BadName));
+        assert_eq!(AltSvcProtocol::from_bytes(b"h2 "), Err(ProtocolError::BadName));
+        assert_eq!(AltSvcProtocol::from_bytes(b"http/1.1"), Err(ProtocolError::BadName));
+        assert_eq!(AltSvcProtocol::from_bytes(b"h%3"), Err(ProtocolError::BadName));
+        assert_eq!(AltSvcProtocol::from_bytes(b"h%zz"), Err(ProtocolError::BadName));
+
+        assert_eq!(AltSvcProtocol::from_bytes(b"h3-29").unwrap().version(),
+                   Some(HttpVersion::HTTP_3));
+        assert_eq!(AltSvcProtocol::from_bytes(b"h2").unwrap().version(), Some(HttpVersion::HTTP_2));
+        assert_eq!(AltSvcProtocol::from_bytes(b"hq").unwrap().version(), None);
+    }
+
+    #[test]
+    fn test_alt_svc_protocol_display() {
+        for s in ["h2", "h3", "http%2F1.1", "http%2F1.0", "h2c", "h3-29", "hq-29",
+                  "w%3Dx%25y"].iter()
+        {
+            assert_eq!(format!("{}", AltSvcProtocol::from_bytes(s.as_bytes()).unwrap()), *s);
+        }
+
+        assert_eq!(format!("{}", AltSvcProtocol::from_bytes(b"http%2f1.0").unwrap()), "http%2F1.0");
+        assert_eq!(format!("{}", AltSvcProtocol::Http(HttpVersion::HTTP_09)), "http%2F0.9");
+        assert_eq!(AltSvcProtocol::Http(HttpVersion::from_parts(1, 2)).to_string(), "http%2F1.2");
+    }
+
+    #[test]
+    fn test_upgrade_list() {
+        let mut iter = UpgradeProtocol::parse_list(b"HTTP/2.0, SHTTP/1.3, IRC/6.9, RTA/x11");
+        assert_eq!(iter.next(), Some(Ok(UpgradeProtocol {
+            name: b"HTTP",
+            version: Some(VersionToken::Http(HttpVersion::HTTP_2)),
+        })));
+        assert_eq!(iter.next().unwrap().unwrap().name, b"SHTTP");
+        assert_eq!(iter.next().unwrap().unwrap().name, b"IRC");
+        assert_eq!(iter.next(), Some(Ok(UpgradeProtocol {
+            name: b"RTA",
+            version: Some(VersionToken::Other(b"x11")),
+        })));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+
+        let names: Vec<_> = UpgradeProtocol::parse_list(b" ,websocket,\t, h2c ,")
+            .map(|p| p.unwrap().name)
+            .collect();
+        assert_eq!(names, [&b"websocket"[..], b"h2c"]);
+
+        assert_eq!(UpgradeProtocol::parse_list(b"").next(), None);
+        assert_eq!(UpgradeProtocol::parse_list(b" , ,").next(), None);
+
+        let mut iter = UpgradeProtocol::parse_list(b"h2c, bad name, websocket");
+        assert_eq!(iter.next().unwrap().unwrap().name, b"h2c");
+        assert_eq!(iter.next(), Some(Err(ProtocolError::BadName)));
+        assert_eq!(iter.next().unwrap().unwrap().name, b"websocket");
+        assert_eq!(iter.next(), None);
+    }
+}
@@ -0,0 +1,172 @@
+//! Versions of protocols sharing the HTTP version syntax, such as RTSP [RFC7826§7.1.1],
+//! SIP [RFC3261§7.1], and ICAP [RFC3507§4.3.2].
+
+use core::fmt;
+
+use grammar::is_token;
+use {HttpVersion, HttpVersionError, parse_number};
+
+/// Version field of the form `name "/" DIGIT "." DIGIT`, such as `RTSP/1.0`.
+///
+/// This generalizes `HttpVersion` over the protocol name, which is kept as given.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct ProtocolVersion<'a> {
+    /// Protocol name, such as `RTSP`.
+    pub name: &'a str,
+    /// Major version number.
+    pub major: u8,
+    /// Minor version number.
+    pub minor: u8,
+}
+
+impl ProtocolVersion<'static> {
+    /// `HTTP/1.1`
+    pub const HTTP_11: Self = ProtocolVersion::new("HTTP", 1, 1);
+    /// `RTSP/1.0`
+    pub const RTSP_10: Self = ProtocolVersion::new("RTSP", 1, 0);
+    /// `RTSP/2.0`
+    pub const RTSP_20: Self = ProtocolVersion::new("RTSP", 2, 0);
+    /// `SIP/2.0`
+    pub const SIP_20: Self = ProtocolVersion::new("SIP", 2, 0);
+    /// `ICAP/1.0`
+    pub const ICAP_10: Self = ProtocolVersion::new("ICAP", 1, 0);
+}
+
+impl<'a> ProtocolVersion<'a> {
+    /// Create a new `ProtocolVersion` with the given name and version parts.
+    pub const fn new(name: &'a str, major: u8, minor: u8) -> Self {
+        ProtocolVersion { name, major, minor }
+    }
+
+    /// Try to parse a version of the protocol with the given name, such as `RTSP` for
+    /// `RTSP/1.0`.
+    ///
+    /// The name is compared case sensitively, as for HTTP, and must be a token.
+    pub fn from_bytes(name: &'a str, s: &[u8]) -> Result<Self, HttpVersionError> {
+        if !is_token(name.as_bytes()) {
+            return Err(HttpVersionError::BadPrefix);
+        }
+
+        let name_len = name.len();
+
+        if !s.starts_with(name.as_bytes()) || s.get(name_len) != Some(&b'/') {
+            return Err(HttpVersionError::BadPrefix);
+        }
+
+        let ver = parse_number(&s[name_len + 1..])?;
+
+        Ok(ProtocolVersion::new(name, ver.major, ver.minor))
+    }
+
+    /// Try to parse a version field with any token as the protocol name.
+    pub fn parse(s: &'a [u8]) -> Result<Self, HttpVersionError> {
+        let name = match s.iter().position(|&b| b == b'/') {
+            Some(idx) if is_token(&s[..idx]) => &s[..idx],
+            _ => return Err(HttpVersionError::BadPrefix),
+        };
+
+        // Token characters are all ASCII.
+        let name = core::str::from_utf8(name).map_err(|_| HttpVersionError::BadPrefix)?;
+
+        ProtocolVersion::from_bytes(name, s)
+    }
+
+    /// Get the version number parts, ignoring the protocol name.
+    pub fn number(&self) -> (u8, u8) {
+        (self.major, self.minor)
+    }
+
+    /// Get the equivalent `HttpVersion`, if the protocol is HTTP.
+    pub fn to_http(&self) -> Option<HttpVersion> {
+        if self.name == "HTTP" {
+            Some(HttpVersion::from_parts(self.major, self.minor))
+        } else {
+            None
+        }
+    }
+}
+
+impl From<HttpVersion> for ProtocolVersion<'static> {
+    fn from(ver: HttpVersion) -> Self {
+        ProtocolVersion::new("HTTP", ver.major, ver.minor)
+    }
+}
+
+/// Writes the version field, such as `RTSP/1.0`, or with the alternate form (`{:#}`)
+/// only the version number, such as `1.0`.
+impl<'a> fmt::Display for ProtocolVersion<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if fmt.alternate() {
+            write!(fmt, "{}.{}", self.major, self.minor)
+        } else {
+            write!(fmt, "{}/{}.{}", self.name, self.major, self.minor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_protocol_version() {
+        assert_eq!(ProtocolVersion::from_bytes("RTSP", b"RTSP/1.0"),
+                   Ok(ProtocolVersion::RTSP_10));
+        assert_eq!(ProtocolVersion::from_bytes("RTSP", b"RTSP/2.0"),
+                   Ok(ProtocolVersion::RTSP_20));
+        assert_eq!(ProtocolVersion::from_bytes("SIP", b"SIP/2.0"),
+                   Ok(ProtocolVersion::SIP_20));
+        assert_eq!(ProtocolVersion::from_bytes("ICAP", b"ICAP/1.0"),
+                   Ok(ProtocolVersion::ICAP_10));
+
+        assert_eq!(ProtocolVersion::from_bytes("RTSP", b"rtsp/1.0"),
+                   Err(HttpVersionError::BadPrefix));
+        assert_eq!(ProtocolVersion::from_bytes("RTSP", b"RTSP1.0"),
+                   Err(HttpVersionError::BadPrefix));
+        assert_eq!(ProtocolVersion::from_bytes("RTSP", b"HTTP/1.0"),
+                   Err(HttpVersionError::BadPrefix));
+        assert_eq!(ProtocolVersion::from_bytes("RTSP", b"RTSP/1.00"),
+                   Err(HttpVersionError::BadLength));
+        assert_eq!(ProtocolVersion::from_bytes("RTSP", b"RTSP/1,0"),
+                   Err(HttpVersionError::BadSeparator));
+        assert_eq!(ProtocolVersion::from_bytes("RTSP", b"RTSP/a.0"),
+                   Err(HttpVersionError::BadDigit));
+        assert_eq!(ProtocolVersion::from_bytes("", b"/1.0"),
+                   Err(HttpVersionError::BadPrefix));
+        assert_eq!(ProtocolVersion::from_bytes("RT SP", b"RT SP/1.0"),
+                   Err(HttpVersionError::BadPrefix));
+
+        for major in 0..10 {
+            for minor in 0..10 {
+                let ver = ProtocolVersion::new("RTSP", major, minor);
+                let s = format!("{}", ver);
+
+                assert_eq!(s, format!("RTSP/{}.{}", major, minor));
+                assert_eq!(format!("{:#}", ver), format!("{}.{}", major, minor));
+                assert_eq!(ProtocolVersion::from_bytes("RTSP", s.as_bytes()), Ok(ver));
+                assert_eq!(ProtocolVersion::parse(s.as_bytes()), Ok(ver));
+                assert_eq!(ver.number(), (major, minor));
+                assert_eq!(ver.to_http(), None);
+
+                let http = HttpVersion::from_parts(major, minor);
+                assert_eq!(ProtocolVersion::from(http).to_http(), Some(http));
+                assert_eq!(format!("{}", ProtocolVersion::from(http)), format!("{}", http));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(ProtocolVersion::parse(b"ICAP/1.0"), Ok(ProtocolVersion::ICAP_10));
+        assert_eq!(ProtocolVersion::parse(b"HTTP/1.1"), Ok(ProtocolVersion::HTTP_11));
+        assert_eq!(ProtocolVersion::parse(b"HTTP/1.1").unwrap().to_http(),
+                   Some(HttpVersion::HTTP_11));
+        assert_eq!(ProtocolVersion::parse(b"x-proto/3.1"),
+                   Ok(ProtocolVersion::new("x-proto", 3, 1)));
+
+        assert_eq!(ProtocolVersion::parse(b"/1.0"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(ProtocolVersion::parse(b"RTSP"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(ProtocolVersion::parse(b"RT SP/1.0"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(ProtocolVersion::parse(b"RTSP/1"), Err(HttpVersionError::BadLength));
+    }
+}
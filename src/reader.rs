@@ -0,0 +1,172 @@
+//! Parsing versions from blocking readers.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use {HttpVersion, HttpVersionError};
+
+impl HttpVersion {
+    /// Read exactly the 8 bytes of a version field from the given reader, such as a
+    /// `TcpStream` or `BufReader`, and parse them.
+    ///
+    /// Short reads and interruptions are retried until the field is complete. Nothing
+    /// past the field is read, so unlike `from_bytes_prefix` this can't check that the
+    /// field isn't followed by another digit.
+    pub fn from_reader<R: io::Read>(mut r: R) -> Result<Self, ReadError> {
+        let mut field = [0; 8];
+        r.read_exact(&mut field).map_err(ReadError::Io)?;
+
+        HttpVersion::from_bytes(&field).map_err(ReadError::Version)
+    }
+}
+
+/// Errors that can occur when reading an `HttpVersion` with `from_reader`.
+#[derive(Debug)]
+pub enum ReadError {
+    /// Reading failed, including when the input ended before the field was complete.
+    Io(io::Error),
+    /// The field was read but was malformed.
+    Version(HttpVersionError),
+}
+
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> Self {
+        ReadError::Io(err)
+    }
+}
+
+impl From<HttpVersionError> for ReadError {
+    fn from(err: HttpVersionError) -> Self {
+        ReadError::Version(err)
+    }
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReadError::Io(ref err) => write!(fmt, "failed to read version field: {}", err),
+            ReadError::Version(ref err) => fmt::Display::fmt(err, fmt),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ReadError::Io(ref err) => Some(err),
+            ReadError::Version(ref err) => Some(err),
+        }
+    }
+}
+
+/// Converts to an error of kind `InvalidData` with the version error as its source, for
+/// using `?` on parse results within `io::Read`-based code.
+impl From<HttpVersionError> for io::Error {
+    fn from(err: HttpVersionError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
+/// Unwraps read failures and converts malformed fields as with `HttpVersionError`.
+impl From<ReadError> for io::Error {
+    fn from(err: ReadError) -> Self {
+        match err {
+            ReadError::Io(err) => err,
+            ReadError::Version(err) => err.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Read;
+
+    /// Reader returning one byte per call, with an interruption before each.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl<'a> Read for Trickle<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+
+            match self.data.split_first() {
+                Some((&b, rest)) if !buf.is_empty() => {
+                    buf[0] = b;
+                    self.data = rest;
+                    Ok(1)
+                },
+                _ => Ok(0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let mut r = &b"HTTP/1.1 200 OK"[..];
+        assert_eq!(HttpVersion::from_reader(&mut r).unwrap(), HttpVersion::HTTP_11);
+        assert_eq!(r, b" 200 OK");
+
+        let r = Trickle { data: b"HTTP/4.2\r\n", interrupt: false };
+        assert_eq!(HttpVersion::from_reader(r).unwrap(), HttpVersion::from_parts(4, 2));
+
+        let r = io::BufReader::new(Trickle { data: b"HTTP/2.0", interrupt: false });
+        assert_eq!(HttpVersion::from_reader(r).unwrap(), HttpVersion::HTTP_2);
+
+        match HttpVersion::from_reader(&b"HTTP/1."[..]) {
+            Err(ReadError::Io(ref err)) if err.kind() == io::ErrorKind::UnexpectedEof => {},
+            res => panic!("unexpected result {:?}", res),
+        }
+
+        match HttpVersion::from_reader(&b"http/1.1"[..]) {
+            Err(ReadError::Version(HttpVersionError::BadPrefix)) => {},
+            res => panic!("unexpected result {:?}", res),
+        }
+
+        match HttpVersion::from_reader(&b"HTTP/1-1"[..]) {
+            Err(ReadError::Version(HttpVersionError::BadSeparator)) => {},
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_read_error() {
+        let err = ReadError::from(HttpVersionError::BadDigit);
+        assert_eq!(err.to_string(), HttpVersionError::BadDigit.to_string());
+        assert!(err.source().is_some());
+
+        let err = ReadError::from(io::Error::from(io::ErrorKind::UnexpectedEof));
+        assert!(err.to_string().starts_with("failed to read version field: "));
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn test_io_error() {
+        fn read_version(mut r: &[u8]) -> io::Result<HttpVersion> {
+            let mut field = [0; 8];
+            r.read_exact(&mut field)?;
+            Ok(HttpVersion::from_bytes(&field)?)
+        }
+
+        assert_eq!(read_version(b"HTTP/1.1").unwrap(), HttpVersion::HTTP_11);
+
+        let err = read_version(b"HTTP/1-1").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.get_ref().and_then(|err| err.downcast_ref()),
+                   Some(&HttpVersionError::BadSeparator));
+        assert_eq!(err.to_string(), HttpVersionError::BadSeparator.to_string());
+
+        let err = io::Error::from(ReadError::Version(HttpVersionError::BadPrefix));
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let err = io::Error::from(HttpVersion::from_reader(&b"HTTP"[..]).unwrap_err());
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,147 @@
+//! Request line parsing [RFC9112§3].
+
+use grammar::{is_token, is_vchar};
+use {HttpVersion, HttpVersionError};
+
+/// HTTP request line [RFC9112§3], borrowing its method and target from the parsed
+/// bytes.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct RequestLine<'a> {
+    /// Request method token, such as `GET`.
+    pub method: &'a [u8],
+    /// Request target, such as `/index.html`.
+    pub target: &'a [u8],
+    /// Protocol version.
+    pub version: HttpVersion,
+}
+
+impl<'a> RequestLine<'a> {
+    /// Try to parse a `RequestLine` from the given bytes in the form
+    /// `method SP request-target SP HTTP-version`.
+    ///
+    /// The bytes must hold exactly the request line, without the trailing CRLF.
+    pub fn from_bytes(s: &'a [u8]) -> Result<Self, RequestLineError> {
+        traced!(request_line, s, RequestLine::parse(s))
+    }
+
+    /// Parse the given request line, as in `from_bytes`.
+    fn parse(s: &'a [u8]) -> Result<Self, RequestLineError> {
+        let (method, rest) = split_space(s).ok_or(RequestLineError::MissingSeparator)?;
+        let (target, version) = split_space(rest).ok_or(RequestLineError::MissingSeparator)?;
+
+        if !is_token(method) {
+            return Err(RequestLineError::BadMethod);
+        }
+
+        if target.is_empty() || !target.iter().all(|&b| is_vchar(b)) {
+            return Err(RequestLineError::BadTarget);
+        }
+
+        Ok(RequestLine {
+            method,
+            target,
+            version: HttpVersion::from_bytes(version)?,
+        })
+    }
+}
+
+/// Split the given bytes around the first space.
+fn split_space(s: &[u8]) -> Option<(&[u8], &[u8])> {
+    s.iter().position(|&b| b == b' ').map(|idx| (&s[..idx], &s[idx + 1..]))
+}
+
+/// Errors that can occur when parsing a `RequestLine`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RequestLineError {
+    /// The line didn't contain the two spaces separating its three fields.
+    MissingSeparator,
+    /// The method was empty or contained a non-token character.
+    BadMethod,
+    /// The request target was empty or contained a non-visible character.
+    BadTarget,
+    /// The version field was malformed.
+    Version(HttpVersionError),
+}
+
+impl From<HttpVersionError> for RequestLineError {
+    fn from(err: HttpVersionError) -> Self {
+        RequestLineError::Version(err)
+    }
+}
+
+impl core::fmt::Display for RequestLineError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use self::RequestLineError::*;
+
+        match *self {
+            MissingSeparator => fmt.write_str("request line is missing a space separator"),
+            BadMethod => fmt.write_str("request line has an invalid method"),
+            BadTarget => fmt.write_str("request line has an invalid request target"),
+            Version(ref err) => core::fmt::Display::fmt(err, fmt),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RequestLineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            RequestLineError::Version(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_request_line() {
+        assert_eq!(RequestLine::from_bytes(b"GET / HTTP/1.1"), Ok(RequestLine {
+            method: b"GET",
+            target: b"/",
+            version: HttpVersion::HTTP_11,
+        }));
+
+        assert_eq!(RequestLine::from_bytes(b"POST /a/b?c=d&e=f HTTP/1.0"), Ok(RequestLine {
+            method: b"POST",
+            target: b"/a/b?c=d&e=f",
+            version: HttpVersion::HTTP_10,
+        }));
+
+        assert_eq!(RequestLine::from_bytes(b"OPTIONS * HTTP/1.1"), Ok(RequestLine {
+            method: b"OPTIONS",
+            target: b"*",
+            version: HttpVersion::HTTP_11,
+        }));
+
+        assert_eq!(RequestLine::from_bytes(b"CONNECT example.com:443 HTTP/1.1"),
+                   Ok(RequestLine {
+            method: b"CONNECT",
+            target: b"example.com:443",
+            version: HttpVersion::HTTP_11,
+        }));
+
+        assert_eq!(RequestLine::from_bytes(b"X-CUSTOM_M http://a/ HTTP/4.2").unwrap().method,
+                   b"X-CUSTOM_M");
+
+        assert_eq!(RequestLine::from_bytes(b"GET"), Err(RequestLineError::MissingSeparator));
+        assert_eq!(RequestLine::from_bytes(b"GET /"), Err(RequestLineError::MissingSeparator));
+        assert_eq!(RequestLine::from_bytes(b""), Err(RequestLineError::MissingSeparator));
+        assert_eq!(RequestLine::from_bytes(b" / HTTP/1.1"), Err(RequestLineError::BadMethod));
+        assert_eq!(RequestLine::from_bytes(b"G(T / HTTP/1.1"), Err(RequestLineError::BadMethod));
+        assert_eq!(RequestLine::from_bytes(b"GET  / HTTP/1.1"), Err(RequestLineError::BadTarget));
+        assert_eq!(RequestLine::from_bytes(b"GET /\x7f HTTP/1.1"),
+                   Err(RequestLineError::BadTarget));
+        assert_eq!(RequestLine::from_bytes(b"GET / HTTP/1.1\r\n"),
+                   Err(RequestLineError::Version(HttpVersionError::BadLength)));
+        assert_eq!(RequestLine::from_bytes(b"GET /  HTTP/1.1"),
+                   Err(RequestLineError::Version(HttpVersionError::BadPrefix)));
+        assert_eq!(RequestLine::from_bytes(b"GET / http/1.1"),
+                   Err(RequestLineError::Version(HttpVersionError::BadPrefix)));
+        assert_eq!(RequestLine::from_bytes(b"GET / HTTP/1.1 x"),
+                   Err(RequestLineError::Version(HttpVersionError::BadLength)));
+    }
+}
@@ -0,0 +1,408 @@
+//! Request target parsing [RFC9112§3.2].
+
+use core::fmt;
+
+use RequestLine;
+
+/// Request target of a request line [RFC9112§3.2], classified by its form and
+/// borrowing its components from the parsed bytes.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum RequestTarget<'a> {
+    /// Origin form `absolute-path ["?" query]` [RFC9112§3.2.1], such as `/a/b?c=d`.
+    Origin {
+        /// Absolute path, starting with `/`.
+        path: &'a [u8],
+        /// Query following the `?`, if present.
+        query: Option<&'a [u8]>,
+    },
+    /// Absolute form `absolute-URI` [RFC9112§3.2.2], such as `http://example.com/a`.
+    Absolute {
+        /// URI scheme, such as `http`.
+        scheme: &'a [u8],
+        /// Authority following the `//`, if present.
+        authority: Option<&'a [u8]>,
+        /// Path, which may be empty.
+        path: &'a [u8],
+        /// Query following the `?`, if present.
+        query: Option<&'a [u8]>,
+    },
+    /// Authority form `uri-host ":" port` [RFC9112§3.2.3], used only with `CONNECT`.
+    Authority {
+        /// Host name or address, including the brackets of an IP literal.
+        host: &'a [u8],
+        /// Port digits.
+        port: &'a [u8],
+    },
+    /// Asterisk form `*` [RFC9112§3.2.4], used only with server-wide `OPTIONS`.
+    Asterisk,
+}
+
+impl<'a> RequestTarget<'a> {
+    /// Try to parse a request target in the origin, absolute, or asterisk form.
+    ///
+    /// The authority form can't be told apart from an absolute URI without knowing the
+    /// method, so use `from_authority` or `for_method` for it.
+    pub fn from_bytes(s: &'a [u8]) -> Result<Self, RequestTargetError> {
+        match s.first() {
+            None => Err(RequestTargetError::BadForm),
+            Some(&b'*') if s.len() == 1 => Ok(RequestTarget::Asterisk),
+            Some(&b'/') => {
+                let (path, query) = split_query(s)?;
+                Ok(RequestTarget::Origin { path, query })
+            },
+            Some(_) => parse_absolute(s),
+        }
+    }
+
+    /// Try to parse a request target in the authority form, such as `example.com:443`.
+    pub fn from_authority(s: &'a [u8]) -> Result<Self, RequestTargetError> {
+        let (host, port) = split_host_port(s).ok_or(RequestTargetError::BadAuthority)?;
+
+        match port {
+            Some(port) => Ok(RequestTarget::Authority { host, port }),
+            None => Err(RequestTargetError::BadAuthority),
+        }
+    }
+
+    /// Try to parse a request target in the form required for the given method
+    /// [RFC9112§3.2].
+    ///
+    /// `CONNECT` requires the authority form, the asterisk form is allowed only with
+    /// `OPTIONS`, and any other method takes the origin or absolute form.
+    pub fn for_method(method: &[u8], s: &'a [u8]) -> Result<Self, RequestTargetError> {
+        if method == b"CONNECT" {
+            return RequestTarget::from_authority(s);
+        }
+
+        match RequestTarget::from_bytes(s)? {
+            RequestTarget::Asterisk if method != b"OPTIONS" => Err(RequestTargetError::BadForm),
+            target => Ok(target),
+        }
+    }
+
+    /// Retrieve the path of the target, if it has one.
+    pub fn path(&self) -> Option<&'a [u8]> {
+        match *self {
+            RequestTarget::Origin { path, .. } | RequestTarget::Absolute { path, .. } => Some(path),
+            RequestTarget::Authority { .. } | RequestTarget::Asterisk => None,
+        }
+    }
+
+    /// Retrieve the query of the target, if it has one.
+    pub fn query(&self) -> Option<&'a [u8]> {
+        match *self {
+            RequestTarget::Origin { query, .. } | RequestTarget::Absolute { query, .. } => query,
+            RequestTarget::Authority { .. } | RequestTarget::Asterisk => None,
+        }
+    }
+}
+
+impl<'a> RequestLine<'a> {
+    /// Try to parse the request target in the form required by the request method.
+    pub fn request_target(&self) -> Result<RequestTarget<'a>, RequestTargetError> {
+        RequestTarget::for_method(self.method, self.target)
+    }
+}
+
+/// Parse an `absolute-URI` [RFC3986§4.3].
+fn parse_absolute<'a>(s: &'a [u8]) -> Result<RequestTarget<'a>, RequestTargetError> {
+    let idx = s.iter().position(|&b| b == b':').ok_or(RequestTargetError::BadForm)?;
+    let (scheme, rest) = (&s[..idx], &s[idx + 1..]);
+
+    if !is_scheme(scheme) {
+        return Err(RequestTargetError::BadScheme);
+    }
+
+    let (rest, query) = match rest.iter().position(|&b| b == b'?') {
+        Some(idx) => (&rest[..idx], Some(&rest[idx + 1..])),
+        None => (rest, None),
+    };
+
+    if let Some(query) = query {
+        if !is_encoded(query, is_query_char) {
+            return Err(RequestTargetError::BadQuery);
+        }
+    }
+
+    let (authority, path) = if rest.starts_with(b"//") {
+        let rest = &rest[2..];
+        let idx = rest.iter().position(|&b| b == b'/').unwrap_or(rest.len());
+
+        if split_authority(&rest[..idx]).is_none() {
+            return Err(RequestTargetError::BadAuthority);
+        }
+
+        (Some(&rest[..idx]), &rest[idx..])
+    } else {
+        (None, rest)
+    };
+
+    if !is_encoded(path, is_path_char) {
+        return Err(RequestTargetError::BadPath);
+    }
+
+    Ok(RequestTarget::Absolute { scheme, authority, path, query })
+}
+
+/// Split the given `absolute-path ["?" query]` into its validated components.
+fn split_query(s: &[u8]) -> Result<(&[u8], Option<&[u8]>), RequestTargetError> {
+    let (path, query) = match s.iter().position(|&b| b == b'?') {
+        Some(idx) => (&s[..idx], Some(&s[idx + 1..])),
+        None => (s, None),
+    };
+
+    if !is_encoded(path, is_path_char) {
+        return Err(RequestTargetError::BadPath);
+    }
+
+    match query {
+        Some(query) if !is_encoded(query, is_query_char) => Err(RequestTargetError::BadQuery),
+        _ => Ok((path, query)),
+    }
+}
+
+/// Split the given `[userinfo "@"] host [":" port]` authority [RFC3986§3.2] into its
+/// host and optional port, if it's well-formed.
+fn split_authority(s: &[u8]) -> Option<(&[u8], Option<&[u8]>)> {
+    let s = match s.iter().position(|&b| b == b'@') {
+        Some(idx) if is_encoded(&s[..idx], |b| is_sub_delim(b) || b == b':') => &s[idx + 1..],
+        Some(_) => return None,
+        None => s,
+    };
+
+    split_host_port(s)
+}
+
+/// Split the given `host [":" port]` into its host and optional port, if it's
+/// well-formed.
+fn split_host_port(s: &[u8]) -> Option<(&[u8], Option<&[u8]>)> {
+    let host_len = if s.first() == Some(&b'[') {
+        let end = s.iter().position(|&b| b == b']')?;
+
+        if !s[1..end].iter().all(|&b| is_unreserved(b) || is_sub_delim(b) || b == b':') {
+            return None;
+        }
+
+        end + 1
+    } else {
+        let end = s.iter().position(|&b| b == b':').unwrap_or(s.len());
+
+        if !is_encoded(&s[..end], is_sub_delim) {
+            return None;
+        }
+
+        end
+    };
+
+    let (host, rest) = (&s[..host_len], &s[host_len..]);
+
+    if host.is_empty() {
+        return None;
+    }
+
+    match rest.split_first() {
+        None => Some((host, None)),
+        Some((&b':', port)) if port.iter().all(u8::is_ascii_digit) => Some((host, Some(port))),
+        Some(_) => None,
+    }
+}
+
+/// Check if the given bytes are a URI scheme [RFC3986§3.1].
+fn is_scheme(s: &[u8]) -> bool {
+    match s.split_first() {
+        Some((first, rest)) => first.is_ascii_alphabetic() && rest.iter().all(|&b| {
+            b.is_ascii_alphanumeric() || b == b'+' || b == b'-' || b == b'.'
+        }),
+        None => false,
+    }
+}
+
+/// Check if the given bytes consist of unreserved characters, percent-encoded octets,
+/// and characters accepted by the given predicate [RFC3986§2].
+fn is_encoded<F: Fn(u8) -> bool>(s: &[u8], allowed: F) -> bool {
+    let mut iter = s.iter();
+
+    while let Some(&b) = iter.next() {
+        if b == b'%' {
+            match (iter.next(), iter.next()) {
+                (Some(hi), Some(lo)) if hi.is_ascii_hexdigit() && lo.is_ascii_hexdigit() => {},
+                _ => return false,
+            }
+        } else if !is_unreserved(b) && !allowed(b) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Check if the given byte is allowed in a path, other than as an unreserved character
+/// or percent-encoded octet [RFC3986§3.3].
+fn is_path_char(b: u8) -> bool {
+    is_sub_delim(b) || b == b':' || b == b'@' || b == b'/'
+}
+
+/// Check if the given byte is allowed in a query, other than as an unreserved character
+/// or percent-encoded octet [RFC3986§3.4].
+fn is_query_char(b: u8) -> bool {
+    is_path_char(b) || b == b'?'
+}
+
+/// Check if the given byte is an unreserved character [RFC3986§2.3].
+fn is_unreserved(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'-' || b == b'.' || b == b'_' || b == b'~'
+}
+
+/// Check if the given byte is a sub-delimiter [RFC3986§2.2].
+fn is_sub_delim(b: u8) -> bool {
+    matches!(b, b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'=')
+}
+
+/// Errors that can occur when parsing a `RequestTarget`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RequestTargetError {
+    /// The target didn't match any form allowed in its position.
+    BadForm,
+    /// The URI scheme was empty or contained an invalid character.
+    BadScheme,
+    /// The authority was empty, malformed, or missing a required port.
+    BadAuthority,
+    /// The path contained an invalid character or percent-encoding.
+    BadPath,
+    /// The query contained an invalid character or percent-encoding.
+    BadQuery,
+}
+
+impl fmt::Display for RequestTargetError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::RequestTargetError::*;
+
+        fmt.write_str(match *self {
+            BadForm => "request target has an invalid form",
+            BadScheme => "request target has an invalid scheme",
+            BadAuthority => "request target has an invalid authority",
+            BadPath => "request target has an invalid path",
+            BadQuery => "request target has an invalid query",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RequestTargetError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_origin() {
+        assert_eq!(RequestTarget::from_bytes(b"/"),
+                   Ok(RequestTarget::Origin { path: b"/", query: None }));
+        assert_eq!(RequestTarget::from_bytes(b"/a/b?c=d&e=f"),
+                   Ok(RequestTarget::Origin { path: b"/a/b", query: Some(b"c=d&e=f") }));
+        assert_eq!(RequestTarget::from_bytes(b"/a%20b?"),
+                   Ok(RequestTarget::Origin { path: b"/a%20b", query: Some(b"") }));
+        assert_eq!(RequestTarget::from_bytes(b"/where?q=now?/x:y@z"),
+                   Ok(RequestTarget::Origin { path: b"/where", query: Some(b"q=now?/x:y@z") }));
+
+        assert_eq!(RequestTarget::from_bytes(b"/a b"), Err(RequestTargetError::BadPath));
+        assert_eq!(RequestTarget::from_bytes(b"/a%2"), Err(RequestTargetError::BadPath));
+        assert_eq!(RequestTarget::from_bytes(b"/a%zz"), Err(RequestTargetError::BadPath));
+        assert_eq!(RequestTarget::from_bytes(b"/a#frag"), Err(RequestTargetError::BadPath));
+        assert_eq!(RequestTarget::from_bytes(b"/a?b#c"), Err(RequestTargetError::BadQuery));
+        assert_eq!(RequestTarget::from_bytes(b"/a?b\"c"), Err(RequestTargetError::BadQuery));
+    }
+
+    #[test]
+    fn test_absolute() {
+        assert_eq!(RequestTarget::from_bytes(b"http://www.example.org/pub/WWW/TheProject.html"),
+                   Ok(RequestTarget::Absolute {
+            scheme: b"http",
+            authority: Some(b"www.example.org"),
+            path: b"/pub/WWW/TheProject.html",
+            query: None,
+        }));
+
+        assert_eq!(RequestTarget::from_bytes(b"https://user:pw@[::1]:8443?x=1"),
+                   Ok(RequestTarget::Absolute {
+            scheme: b"https",
+            authority: Some(b"user:pw@[::1]:8443"),
+            path: b"",
+            query: Some(b"x=1"),
+        }));
+
+        assert_eq!(RequestTarget::from_bytes(b"urn:isbn:0451450523"),
+                   Ok(RequestTarget::Absolute {
+            scheme: b"urn",
+            authority: None,
+            path: b"isbn:0451450523",
+            query: None,
+        }));
+
+        assert_eq!(RequestTarget::from_bytes(b"example.com:443").unwrap().path(),
+                   Some(&b"443"[..]));
+
+        assert_eq!(RequestTarget::from_bytes(b""), Err(RequestTargetError::BadForm));
+        assert_eq!(RequestTarget::from_bytes(b"example.com"), Err(RequestTargetError::BadForm));
+        assert_eq!(RequestTarget::from_bytes(b"**"), Err(RequestTargetError::BadForm));
+        assert_eq!(RequestTarget::from_bytes(b"1http://a/"), Err(RequestTargetError::BadScheme));
+        assert_eq!(RequestTarget::from_bytes(b"://a/"), Err(RequestTargetError::BadScheme));
+        assert_eq!(RequestTarget::from_bytes(b"http:///a"), Err(RequestTargetError::BadAuthority));
+        assert_eq!(RequestTarget::from_bytes(b"http://a:b/"),
+                   Err(RequestTargetError::BadAuthority));
+        assert_eq!(RequestTarget::from_bytes(b"http://[::1/"),
+                   Err(RequestTargetError::BadAuthority));
+        assert_eq!(RequestTarget::from_bytes(b"http://a/b c"), Err(RequestTargetError::BadPath));
+        assert_eq!(RequestTarget::from_bytes(b"http://a/#b"), Err(RequestTargetError::BadPath));
+        assert_eq!(RequestTarget::from_bytes(b"http://a/?b c"),
+                   Err(RequestTargetError::BadQuery));
+    }
+
+    #[test]
+    fn test_authority() {
+        assert_eq!(RequestTarget::from_authority(b"www.example.com:80"),
+                   Ok(RequestTarget::Authority { host: b"www.example.com", port: b"80" }));
+        assert_eq!(RequestTarget::from_authority(b"[2001:db8::1]:443"),
+                   Ok(RequestTarget::Authority { host: b"[2001:db8::1]", port: b"443" }));
+        assert_eq!(RequestTarget::from_authority(b"10.0.0.1:"),
+                   Ok(RequestTarget::Authority { host: b"10.0.0.1", port: b"" }));
+
+        assert_eq!(RequestTarget::from_authority(b"example.com"),
+                   Err(RequestTargetError::BadAuthority));
+        assert_eq!(RequestTarget::from_authority(b":443"), Err(RequestTargetError::BadAuthority));
+        assert_eq!(RequestTarget::from_authority(b"user@example.com:443"),
+                   Err(RequestTargetError::BadAuthority));
+        assert_eq!(RequestTarget::from_authority(b"example.com:44a"),
+                   Err(RequestTargetError::BadAuthority));
+        assert_eq!(RequestTarget::from_authority(b"/a"), Err(RequestTargetError::BadAuthority));
+    }
+
+    #[test]
+    fn test_asterisk() {
+        assert_eq!(RequestTarget::from_bytes(b"*"), Ok(RequestTarget::Asterisk));
+        assert_eq!(RequestTarget::Asterisk.path(), None);
+        assert_eq!(RequestTarget::Asterisk.query(), None);
+    }
+
+    #[test]
+    fn test_for_method() {
+        assert_eq!(RequestTarget::for_method(b"OPTIONS", b"*"), Ok(RequestTarget::Asterisk));
+        assert_eq!(RequestTarget::for_method(b"GET", b"*"), Err(RequestTargetError::BadForm));
+        assert_eq!(RequestTarget::for_method(b"CONNECT", b"example.com:443"),
+                   Ok(RequestTarget::Authority { host: b"example.com", port: b"443" }));
+        assert_eq!(RequestTarget::for_method(b"CONNECT", b"/"),
+                   Err(RequestTargetError::BadAuthority));
+        assert_eq!(RequestTarget::for_method(b"GET", b"example.com:443").unwrap().path(),
+                   Some(&b"443"[..]));
+
+        let line = RequestLine::from_bytes(b"GET /a?b HTTP/1.1").unwrap();
+        let target = line.request_target().unwrap();
+        assert_eq!(target.path(), Some(&b"/a"[..]));
+        assert_eq!(target.query(), Some(&b"b"[..]));
+
+        let line = RequestLine::from_bytes(b"CONNECT example.com:443 HTTP/1.1").unwrap();
+        assert_eq!(line.request_target(),
+                   Ok(RequestTarget::Authority { host: b"example.com", port: b"443" }));
+    }
+}
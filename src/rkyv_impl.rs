@@ -0,0 +1,52 @@
+//! Conversions for archived versions.
+
+use {ArchivedHttpVersion, HttpVersion};
+
+impl ArchivedHttpVersion {
+    /// Get the version stored in the archive.
+    pub fn get(&self) -> HttpVersion {
+        HttpVersion::from(self)
+    }
+}
+
+impl<'a> From<&'a ArchivedHttpVersion> for HttpVersion {
+    fn from(ver: &'a ArchivedHttpVersion) -> Self {
+        HttpVersion { major: ver.major, minor: ver.minor }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rkyv::rancor::Error;
+
+    #[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize, Debug, PartialEq)]
+    struct Record {
+        status: u16,
+        version: HttpVersion,
+    }
+
+    #[test]
+    fn test_rkyv() {
+        for major in 0..10 {
+            for minor in 0..10 {
+                let ver = HttpVersion::from_parts(major, minor);
+                let bytes = rkyv::to_bytes::<Error>(&ver).unwrap();
+                let archived = rkyv::access::<ArchivedHttpVersion, Error>(&bytes).unwrap();
+
+                assert_eq!(archived, &ver);
+                assert_eq!(archived.get(), ver);
+                assert_eq!(rkyv::deserialize::<HttpVersion, Error>(archived).unwrap(), ver);
+            }
+        }
+
+        let rec = Record { status: 200, version: HttpVersion::HTTP_11 };
+        let bytes = rkyv::to_bytes::<Error>(&rec).unwrap();
+        let archived = rkyv::access::<ArchivedRecord, Error>(&bytes).unwrap();
+
+        assert_eq!(archived.status, 200);
+        assert_eq!(archived.version, HttpVersion::HTTP_11);
+        assert!(archived.version.get().supports_chunked_transfer());
+        assert_eq!(rkyv::deserialize::<Record, Error>(archived).unwrap(), rec);
+    }
+}
@@ -0,0 +1,41 @@
+//! JSON schema of the human-readable `HttpVersion` representation.
+
+use alloc::borrow::Cow;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+
+use HttpVersion;
+
+/// Describes the `HTTP/x.y` string used by human-readable serde formats.
+impl JsonSchema for HttpVersion {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("HttpVersion")
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("uhttp_version::HttpVersion")
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        schemars::json_schema!({
+            "type": "string",
+            "pattern": r"^HTTP/[0-9]\.[0-9]$",
+            "description": "HTTP protocol version, such as `HTTP/1.1`.",
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_schema() {
+        let schema = schemars::schema_for!(HttpVersion);
+
+        assert_eq!(schema.get("type").and_then(|v| v.as_str()), Some("string"));
+        assert_eq!(schema.get("pattern").and_then(|v| v.as_str()),
+                   Some(r"^HTTP/[0-9]\.[0-9]$"));
+        assert_eq!(schema.get("title").and_then(|v| v.as_str()), Some("HttpVersion"));
+    }
+}
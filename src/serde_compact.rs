@@ -0,0 +1,77 @@
+//! Compact binary serde representation of `HttpVersion`.
+//!
+//! The version is serialized as exactly two bytes, `[major, minor]`, through
+//! `serialize_bytes`, which most binary formats emit without any extra framing beyond
+//! their usual byte-string length. Use it on a field with
+//! `#[serde(with = "uhttp_version::serde_compact")]`.
+
+use core::fmt;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::Serializer;
+
+use HttpVersion;
+
+/// Serialize the given version as the two bytes `[major, minor]`.
+pub fn serialize<S: Serializer>(ver: &HttpVersion, ser: S) -> Result<S::Ok, S::Error> {
+    ser.serialize_bytes(&[ver.major, ver.minor])
+}
+
+/// Deserialize a version from exactly two bytes `[major, minor]`.
+pub fn deserialize<'de, D: Deserializer<'de>>(de: D) -> Result<HttpVersion, D::Error> {
+    de.deserialize_bytes(CompactVisitor)
+}
+
+/// Visitor accepting a 2-byte payload of single-digit version parts.
+struct CompactVisitor;
+
+impl<'de> Visitor<'de> for CompactVisitor {
+    type Value = HttpVersion;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("2 bytes holding single-digit HTTP major and minor versions")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        match *v {
+            [major, minor] => HttpVersion::try_from_parts(major, minor)
+                .map_err(|_| E::invalid_value(de::Unexpected::Bytes(v), &self)),
+            _ => Err(E::invalid_length(v.len(), &self)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bincode;
+
+    #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+    struct Record {
+        #[serde(with = "::serde_compact")]
+        version: HttpVersion,
+    }
+
+    #[test]
+    fn test_serde_compact() {
+        let rec = Record { version: HttpVersion::from_parts(1, 1) };
+        let buf = bincode::serialize(&rec).unwrap();
+        assert_eq!(&buf[..], &[2, 0, 0, 0, 0, 0, 0, 0, 1, 1]);
+        assert_eq!(bincode::deserialize::<Record>(&buf).unwrap(), rec);
+
+        for &(major, minor) in &[(0, 9), (1, 0), (2, 0), (9, 9)] {
+            let rec = Record { version: HttpVersion::from_parts(major, minor) };
+            let buf = bincode::serialize(&rec).unwrap();
+            assert_eq!(bincode::deserialize::<Record>(&buf).unwrap(), rec);
+        }
+
+        let short = bincode::serialize(&[1u8][..]).unwrap();
+        assert!(bincode::deserialize::<Record>(&short).is_err());
+
+        let long = bincode::serialize(&[1u8, 1, 1][..]).unwrap();
+        assert!(bincode::deserialize::<Record>(&long).is_err());
+
+        let bad = bincode::serialize(&[1u8, 10][..]).unwrap();
+        assert!(bincode::deserialize::<Record>(&bad).is_err());
+    }
+}
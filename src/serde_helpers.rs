@@ -0,0 +1,232 @@
+//! Alternative serde representations of `HttpVersion`, for formats that need the same
+//! representation regardless of whether they're human-readable.
+//!
+//! Each submodule is used on a field with `#[serde(with = "...")]`, such as
+//! `#[serde(with = "uhttp_version::serde_helpers::as_map")]`, and deserializes only
+//! versions with single-digit parts.
+
+/// Serialize and deserialize a version as its `HTTP/x.y` string in every format.
+pub mod as_string {
+    use serde::de::Deserializer;
+    use serde::ser::Serializer;
+
+    use HttpVersion;
+    use serde_impl::StringVisitor;
+
+    /// Serialize the given version as its `HTTP/x.y` string.
+    pub fn serialize<S: Serializer>(ver: &HttpVersion, ser: S) -> Result<S::Ok, S::Error> {
+        ser.collect_str(ver)
+    }
+
+    /// Deserialize a version from its `HTTP/x.y` string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(de: D) -> Result<HttpVersion, D::Error> {
+        de.deserialize_str(StringVisitor)
+    }
+}
+
+/// Serialize and deserialize a version as its `(major, minor)` tuple in every format.
+pub mod as_tuple {
+    use serde::de::Deserializer;
+    use serde::ser::{SerializeTuple, Serializer};
+
+    use HttpVersion;
+    use serde_impl::TupleVisitor;
+
+    /// Serialize the given version as its `(major, minor)` tuple.
+    pub fn serialize<S: Serializer>(ver: &HttpVersion, ser: S) -> Result<S::Ok, S::Error> {
+        let mut tup = ser.serialize_tuple(2)?;
+        tup.serialize_element(&ver.major)?;
+        tup.serialize_element(&ver.minor)?;
+        tup.end()
+    }
+
+    /// Deserialize a version from its `(major, minor)` tuple.
+    pub fn deserialize<'de, D: Deserializer<'de>>(de: D) -> Result<HttpVersion, D::Error> {
+        de.deserialize_tuple(2, TupleVisitor)
+    }
+}
+
+/// Serialize and deserialize a version as a `{major, minor}` struct in every format.
+///
+/// Unknown fields are ignored when deserializing, as with a derived `Deserialize`.
+pub mod as_map {
+    use core::fmt;
+
+    use serde::de::{self, Deserialize, Deserializer, IgnoredAny, MapAccess, SeqAccess,
+                    Visitor};
+    use serde::ser::{SerializeStruct, Serializer};
+
+    use HttpVersion;
+    use serde_impl::check_parts;
+
+    /// Names of the struct fields.
+    const FIELDS: &[&str] = &["major", "minor"];
+
+    /// Serialize the given version as a `{major, minor}` struct.
+    pub fn serialize<S: Serializer>(ver: &HttpVersion, ser: S) -> Result<S::Ok, S::Error> {
+        let mut st = ser.serialize_struct("HttpVersion", 2)?;
+        st.serialize_field("major", &ver.major)?;
+        st.serialize_field("minor", &ver.minor)?;
+        st.end()
+    }
+
+    /// Deserialize a version from a `{major, minor}` struct.
+    pub fn deserialize<'de, D: Deserializer<'de>>(de: D) -> Result<HttpVersion, D::Error> {
+        de.deserialize_struct("HttpVersion", FIELDS, MapVisitor)
+    }
+
+    /// Visitor accepting the version struct as a map, or as a sequence in formats that
+    /// serialize structs without their field names.
+    struct MapVisitor;
+
+    impl<'de> Visitor<'de> for MapVisitor {
+        type Value = HttpVersion;
+
+        fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.write_str("a {major, minor} HTTP version struct")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut major = None;
+            let mut minor = None;
+
+            while let Some(field) = map.next_key()? {
+                match field {
+                    Field::Major if major.is_some() => {
+                        return Err(de::Error::duplicate_field("major"));
+                    },
+                    Field::Minor if minor.is_some() => {
+                        return Err(de::Error::duplicate_field("minor"));
+                    },
+                    Field::Major => major = Some(map.next_value::<u8>()?),
+                    Field::Minor => minor = Some(map.next_value::<u8>()?),
+                    Field::Other => {
+                        map.next_value::<IgnoredAny>()?;
+                    },
+                }
+            }
+
+            let major = major.ok_or_else(|| de::Error::missing_field("major"))?;
+            let minor = minor.ok_or_else(|| de::Error::missing_field("minor"))?;
+
+            check_parts(major, minor, &self)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let major: u8 = seq.next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let minor: u8 = seq.next_element()?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+            check_parts(major, minor, &self)
+        }
+    }
+
+    /// Field of the version struct.
+    enum Field {
+        Major,
+        Minor,
+        Other,
+    }
+
+    impl<'de> Deserialize<'de> for Field {
+        fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+            de.deserialize_identifier(FieldVisitor)
+        }
+    }
+
+    /// Visitor accepting a field name or index.
+    struct FieldVisitor;
+
+    impl<'de> Visitor<'de> for FieldVisitor {
+        type Value = Field;
+
+        fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.write_str("an HTTP version field name")
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            Ok(match v {
+                0 => Field::Major,
+                1 => Field::Minor,
+                _ => Field::Other,
+            })
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            Ok(match v {
+                "major" => Field::Major,
+                "minor" => Field::Minor,
+                _ => Field::Other,
+            })
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            Ok(match v {
+                b"major" => Field::Major,
+                b"minor" => Field::Minor,
+                _ => Field::Other,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use HttpVersion;
+    use bincode;
+    use serde_json;
+
+    #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+    struct Record {
+        #[serde(with = "::serde_helpers::as_string")]
+        string: HttpVersion,
+        #[serde(with = "::serde_helpers::as_tuple")]
+        tuple: HttpVersion,
+        #[serde(with = "::serde_helpers::as_map")]
+        map: HttpVersion,
+    }
+
+    #[test]
+    fn test_json() {
+        let ver = HttpVersion::HTTP_11;
+        let rec = Record { string: ver, tuple: ver, map: ver };
+        let json = r#"{"string":"HTTP/1.1","tuple":[1,1],"map":{"major":1,"minor":1}}"#;
+
+        assert_eq!(serde_json::to_string(&rec).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Record>(json).unwrap(), rec);
+
+        let json = r#"{"string":"HTTP/2.0","tuple":[2,0],"map":{"minor":0,"x":[],"major":2}}"#;
+        let ver = HttpVersion::HTTP_2;
+        assert_eq!(serde_json::from_str::<Record>(json).unwrap(),
+                   Record { string: ver, tuple: ver, map: ver });
+
+        for json in [
+            r#"{"string":"http/1.1","tuple":[1,1],"map":{"major":1,"minor":1}}"#,
+            r#"{"string":[1,1],"tuple":[1,1],"map":{"major":1,"minor":1}}"#,
+            r#"{"string":"HTTP/1.1","tuple":[1,10],"map":{"major":1,"minor":1}}"#,
+            r#"{"string":"HTTP/1.1","tuple":[1],"map":{"major":1,"minor":1}}"#,
+            r#"{"string":"HTTP/1.1","tuple":"HTTP/1.1","map":{"major":1,"minor":1}}"#,
+            r#"{"string":"HTTP/1.1","tuple":[1,1],"map":{"major":1}}"#,
+            r#"{"string":"HTTP/1.1","tuple":[1,1],"map":{"major":1,"minor":1,"major":1}}"#,
+            r#"{"string":"HTTP/1.1","tuple":[1,1],"map":{"major":12,"minor":34}}"#,
+        ].iter() {
+            assert!(serde_json::from_str::<Record>(json).is_err(), "{}", json);
+        }
+    }
+
+    #[test]
+    fn test_binary() {
+        let ver = HttpVersion::from_parts(4, 2);
+        let rec = Record { string: ver, tuple: ver, map: ver };
+        let buf = bincode::serialize(&rec).unwrap();
+
+        assert_eq!(&buf[..], &[8, 0, 0, 0, 0, 0, 0, 0, b'H', b'T', b'T', b'P', b'/', b'4', b'.',
+                               b'2', 4, 2, 4, 2][..]);
+        assert_eq!(bincode::deserialize::<Record>(&buf).unwrap(), rec);
+
+        let mut bad = buf.clone();
+        bad[19] = 10;
+        assert!(bincode::deserialize::<Record>(&bad).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Default serde representation of `HttpVersion`.
+//!
+//! Human-readable formats use the canonical `HTTP/x.y` string, and compact binary formats
+//! use the `(major, minor)` tuple.
+
+use core::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+use HttpVersion;
+
+impl Serialize for HttpVersion {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        if ser.is_human_readable() {
+            ser.collect_str(self)
+        } else {
+            let mut tup = ser.serialize_tuple(2)?;
+            tup.serialize_element(&self.major)?;
+            tup.serialize_element(&self.minor)?;
+            tup.end()
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HttpVersion {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        if de.is_human_readable() {
+            de.deserialize_str(StringVisitor)
+        } else {
+            de.deserialize_tuple(2, TupleVisitor)
+        }
+    }
+}
+
+/// Visitor accepting only the version string.
+pub(crate) struct StringVisitor;
+
+impl<'de> Visitor<'de> for StringVisitor {
+    type Value = HttpVersion;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("an HTTP version string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        HttpVersion::from_bytes(v.as_bytes())
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+}
+
+/// Visitor accepting only the `(major, minor)` tuple.
+pub(crate) struct TupleVisitor;
+
+impl<'de> Visitor<'de> for TupleVisitor {
+    type Value = HttpVersion;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("a (major, minor) HTTP version tuple")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let major: u8 = seq.next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let minor: u8 = seq.next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        check_parts(major, minor, &self)
+    }
+}
+
+/// Create a version from the given deserialized parts, failing if either isn't a single
+/// digit.
+pub(crate) fn check_parts<V, E>(major: u8, minor: u8, exp: &V) -> Result<HttpVersion, E>
+    where V: de::Expected, E: de::Error
+{
+    HttpVersion::try_from_parts(major, minor).map_err(|_| {
+        E::invalid_value(de::Unexpected::Unsigned(major.max(minor) as u64), exp)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bincode;
+    use serde_json;
+
+    #[test]
+    fn test_serde_human_readable() {
+        let ver = HttpVersion::from_parts(1, 1);
+        assert_eq!(serde_json::to_string(&ver).unwrap(), r#""HTTP/1.1""#);
+        assert_eq!(serde_json::from_str::<HttpVersion>(r#""HTTP/1.1""#).unwrap(), ver);
+        assert_eq!(serde_json::from_str::<HttpVersion>(r#""HTTP/0.9""#).unwrap(),
+                   HttpVersion::from_parts(0, 9));
+
+        assert!(serde_json::from_str::<HttpVersion>(r#""http/1.1""#).is_err());
+        assert!(serde_json::from_str::<HttpVersion>(r#""HTTP/1.10""#).is_err());
+        assert!(serde_json::from_str::<HttpVersion>("[1, 1]").is_err());
+        assert!(serde_json::from_str::<HttpVersion>("11").is_err());
+    }
+
+    #[test]
+    fn test_serde_binary() {
+        let ver = HttpVersion::from_parts(1, 1);
+        let buf = bincode::serialize(&ver).unwrap();
+        assert_eq!(&buf[..], &[1, 1]);
+        assert_eq!(bincode::deserialize::<HttpVersion>(&buf).unwrap(), ver);
+
+        for major in 0..10 {
+            for minor in 0..10 {
+                let ver = HttpVersion::from_parts(major, minor);
+                let buf = bincode::serialize(&ver).unwrap();
+                assert_eq!(bincode::deserialize::<HttpVersion>(&buf).unwrap(), ver);
+            }
+        }
+
+        assert!(bincode::deserialize::<HttpVersion>(&[1, 10]).is_err());
+        assert!(bincode::deserialize::<HttpVersion>(&[42, 0]).is_err());
+        assert!(bincode::deserialize::<HttpVersion>(&[1]).is_err());
+    }
+}
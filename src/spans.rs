@@ -0,0 +1,167 @@
+//! Start line tokenizing into byte spans [RFC9112§2.1].
+
+use core::fmt;
+use core::ops::Range;
+
+use {HttpVersion, HttpVersionError};
+
+/// Byte spans of the three fields of a start line, with only the version field parsed.
+///
+/// The spans are offsets into the tokenized line, so they can be kept after the line's
+/// buffer is moved or reused.
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct StartLineSpans {
+    /// Spans of the three fields, in the order they appear in the line.
+    pub fields: (Range<usize>, Range<usize>, Range<usize>),
+    /// Protocol version parsed from the version field.
+    pub version: HttpVersion,
+}
+
+impl StartLineSpans {
+    /// Split the given request line, without the trailing CRLF, into the spans of its
+    /// `method SP request-target SP HTTP-version` fields.
+    ///
+    /// The method and target are split around the first two spaces but not validated.
+    pub fn from_request_line(s: &[u8]) -> Result<Self, SpanError> {
+        let method = 0..find_space(s, 0).ok_or(SpanError::MissingSeparator)?;
+        let target = method.end + 1..find_space(s, method.end + 1)
+            .ok_or(SpanError::MissingSeparator)?;
+        let field = target.end + 1..s.len();
+
+        let version = HttpVersion::from_bytes(&s[field.clone()]).map_err(|err| {
+            SpanError::Version { err, start: field.start, end: field.end }
+        })?;
+
+        Ok(StartLineSpans { fields: (method, target, field), version })
+    }
+
+    /// Split the given status line, without the trailing CRLF, into the spans of its
+    /// `HTTP-version SP status-code SP [reason-phrase]` fields.
+    ///
+    /// The status code and reason phrase are split around the first two spaces but not
+    /// validated. An empty reason phrase is accepted with or without its leading space.
+    pub fn from_status_line(s: &[u8]) -> Result<Self, SpanError> {
+        let field = 0..find_space(s, 0).ok_or(SpanError::MissingSeparator)?;
+
+        let version = HttpVersion::from_bytes(&s[field.clone()]).map_err(|err| {
+            SpanError::Version { err, start: field.start, end: field.end }
+        })?;
+
+        let code = field.end + 1..find_space(s, field.end + 1).unwrap_or(s.len());
+        let reason = (code.end + 1).min(s.len())..s.len();
+
+        Ok(StartLineSpans { fields: (field, code, reason), version })
+    }
+}
+
+/// Find the index of the first space at or after the given start index.
+fn find_space(s: &[u8], start: usize) -> Option<usize> {
+    s[start..].iter().position(|&b| b == b' ').map(|idx| start + idx)
+}
+
+/// Errors that can occur when tokenizing a start line into `StartLineSpans`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SpanError {
+    /// The line didn't contain the spaces separating its fields.
+    MissingSeparator,
+    /// The version field spanning the given byte offsets was malformed.
+    Version {
+        /// Reason the version field was rejected.
+        err: HttpVersionError,
+        /// Offset of the first byte of the field.
+        start: usize,
+        /// Offset just past the last byte of the field.
+        end: usize,
+    },
+}
+
+impl SpanError {
+    /// Retrieve the span of the offending field, if the error is confined to one.
+    pub fn span(&self) -> Option<Range<usize>> {
+        match *self {
+            SpanError::MissingSeparator => None,
+            SpanError::Version { start, end, .. } => Some(start..end),
+        }
+    }
+}
+
+impl fmt::Display for SpanError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SpanError::MissingSeparator => fmt.write_str("start line is missing a space separator"),
+            SpanError::Version { ref err, .. } => fmt::Display::fmt(err, fmt),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SpanError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            SpanError::Version { ref err, .. } => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_request_line() {
+        let line = b"GET /a?b HTTP/1.1";
+        let spans = StartLineSpans::from_request_line(line).unwrap();
+
+        assert_eq!(spans, StartLineSpans {
+            fields: (0..3, 4..8, 9..17),
+            version: HttpVersion::HTTP_11,
+        });
+        assert_eq!(&line[spans.fields.0], b"GET");
+        assert_eq!(&line[spans.fields.1], b"/a?b");
+        assert_eq!(&line[spans.fields.2], b"HTTP/1.1");
+
+        assert_eq!(StartLineSpans::from_request_line(b"  HTTP/1.0").unwrap().fields,
+                   (0..0, 1..1, 2..10));
+
+        assert_eq!(StartLineSpans::from_request_line(b""), Err(SpanError::MissingSeparator));
+        assert_eq!(StartLineSpans::from_request_line(b"GET /"), Err(SpanError::MissingSeparator));
+        assert_eq!(StartLineSpans::from_request_line(b"GET / http/1.1"), Err(SpanError::Version {
+            err: HttpVersionError::BadPrefix,
+            start: 6,
+            end: 14,
+        }));
+        assert_eq!(StartLineSpans::from_request_line(b"GET / HTTP/1.1 x").unwrap_err().span(),
+                   Some(6..16));
+    }
+
+    #[test]
+    fn test_status_line() {
+        let line = b"HTTP/1.1 404 Not Found";
+        let spans = StartLineSpans::from_status_line(line).unwrap();
+
+        assert_eq!(spans, StartLineSpans {
+            fields: (0..8, 9..12, 13..22),
+            version: HttpVersion::HTTP_11,
+        });
+        assert_eq!(&line[spans.fields.0], b"HTTP/1.1");
+        assert_eq!(&line[spans.fields.1], b"404");
+        assert_eq!(&line[spans.fields.2], b"Not Found");
+
+        assert_eq!(StartLineSpans::from_status_line(b"HTTP/1.0 200 ").unwrap().fields,
+                   (0..8, 9..12, 13..13));
+        assert_eq!(StartLineSpans::from_status_line(b"HTTP/1.0 200").unwrap().fields,
+                   (0..8, 9..12, 12..12));
+        assert_eq!(StartLineSpans::from_status_line(b"HTTP/2.0 ").unwrap().fields,
+                   (0..8, 9..9, 9..9));
+
+        assert_eq!(StartLineSpans::from_status_line(b"HTTP/1.1"), Err(SpanError::MissingSeparator));
+        assert_eq!(StartLineSpans::from_status_line(b"HTTP/1.10 200 OK"), Err(SpanError::Version {
+            err: HttpVersionError::BadLength,
+            start: 0,
+            end: 9,
+        }));
+        assert_eq!(SpanError::MissingSeparator.span(), None);
+    }
+}
@@ -0,0 +1,138 @@
+//! Start line parsing for either message direction [RFC9112§2.1].
+
+use core::fmt;
+
+use {HttpVersion, RequestLine, RequestLineError, StatusLine, StatusLineError};
+
+/// HTTP start line [RFC9112§2.1], which is a request line in requests and a status line
+/// in responses.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum StartLine<'a> {
+    /// Request line of a request.
+    Request(RequestLine<'a>),
+    /// Status line of a response.
+    Status(StatusLine<'a>),
+}
+
+impl<'a> StartLine<'a> {
+    /// Try to parse a `StartLine` from the given bytes, without the trailing CRLF.
+    ///
+    /// Lines beginning with the `HTTP/` version name are parsed as status lines, and all
+    /// others as request lines.
+    pub fn from_bytes(s: &'a [u8]) -> Result<Self, StartLineError> {
+        if s.starts_with(b"HTTP/") {
+            StatusLine::from_bytes(s).map(StartLine::Status).map_err(StartLineError::Status)
+        } else {
+            RequestLine::from_bytes(s).map(StartLine::Request).map_err(StartLineError::Request)
+        }
+    }
+
+    /// Try to parse a `StartLine` as in `from_bytes`, additionally treating lines
+    /// beginning with `ICY ` as Shoutcast status lines given the `icy` version, as in
+    /// `StatusLine::from_bytes_icy`.
+    pub fn from_bytes_icy(s: &'a [u8], icy: HttpVersion) -> Result<Self, StartLineError> {
+        if s.starts_with(b"ICY ") {
+            StatusLine::from_bytes_icy(s, icy)
+                .map(StartLine::Status)
+                .map_err(StartLineError::Status)
+        } else {
+            StartLine::from_bytes(s)
+        }
+    }
+
+    /// Retrieve the protocol version of the line.
+    pub fn version(&self) -> HttpVersion {
+        match *self {
+            StartLine::Request(ref line) => line.version,
+            StartLine::Status(ref line) => line.version,
+        }
+    }
+}
+
+/// Errors that can occur when parsing a `StartLine`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum StartLineError {
+    /// The line looked like a request line but was malformed.
+    Request(RequestLineError),
+    /// The line looked like a status line but was malformed.
+    Status(StatusLineError),
+}
+
+impl fmt::Display for StartLineError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StartLineError::Request(ref err) => fmt::Display::fmt(err, fmt),
+            StartLineError::Status(ref err) => fmt::Display::fmt(err, fmt),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StartLineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            StartLineError::Request(ref err) => Some(err),
+            StartLineError::Status(ref err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {HttpVersionError, StatusCode};
+
+    #[test]
+    fn test_start_line() {
+        let line = StartLine::from_bytes(b"GET /index.html HTTP/1.1").unwrap();
+        assert_eq!(line, StartLine::Request(RequestLine {
+            method: b"GET",
+            target: b"/index.html",
+            version: HttpVersion::HTTP_11,
+        }));
+        assert_eq!(line.version(), HttpVersion::HTTP_11);
+
+        let line = StartLine::from_bytes(b"HTTP/1.0 404 Not Found").unwrap();
+        assert_eq!(line, StartLine::Status(StatusLine {
+            version: HttpVersion::HTTP_10,
+            code: StatusCode::from_u16(404).unwrap(),
+            reason: b"Not Found",
+        }));
+        assert_eq!(line.version(), HttpVersion::HTTP_10);
+
+        assert_eq!(StartLine::from_bytes(b"HTTP/1.1 20 OK"),
+                   Err(StartLineError::Status(StatusLineError::BadStatusCode)));
+        assert_eq!(StartLine::from_bytes(b"HTTP/1.1"),
+                   Err(StartLineError::Status(StatusLineError::MissingSeparator)));
+        assert_eq!(StartLine::from_bytes(b"GET / HTTP/1.x"),
+                   Err(StartLineError::Request(RequestLineError::Version(
+                       HttpVersionError::BadDigit))));
+        assert_eq!(StartLine::from_bytes(b"http/1.1 200 OK"),
+                   Err(StartLineError::Request(RequestLineError::BadMethod)));
+        assert_eq!(StartLine::from_bytes(b"GET / http/1.1"),
+                   Err(StartLineError::Request(RequestLineError::Version(
+                       HttpVersionError::BadPrefix))));
+        assert_eq!(StartLine::from_bytes(b""),
+                   Err(StartLineError::Request(RequestLineError::MissingSeparator)));
+    }
+
+    #[test]
+    fn test_start_line_icy() {
+        let v10 = HttpVersion::HTTP_10;
+
+        assert_eq!(StartLine::from_bytes_icy(b"ICY 200 OK", v10), Ok(StartLine::Status(StatusLine {
+            version: v10,
+            code: StatusCode::from_u16(200).unwrap(),
+            reason: b"OK",
+        })));
+        assert_eq!(StartLine::from_bytes_icy(b"GET / HTTP/1.1", v10).unwrap().version(),
+                   HttpVersion::HTTP_11);
+        assert_eq!(StartLine::from_bytes_icy(b"ICY 999 OK", v10),
+                   Err(StartLineError::Status(StatusLineError::BadStatusCode)));
+
+        assert_eq!(StartLine::from_bytes(b"ICY 200 OK"),
+                   Err(StartLineError::Request(RequestLineError::Version(
+                       HttpVersionError::BadPrefix))));
+    }
+}
@@ -0,0 +1,218 @@
+//! Response status code parsing and classification [RFC9110§15].
+
+use core::fmt;
+
+use grammar::to_digit;
+
+/// HTTP response status code [RFC9110§15], guaranteed to be within 100 to 599.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct StatusCode(u16);
+
+impl StatusCode {
+    /// Try to create a `StatusCode` from the given numeric code.
+    pub const fn from_u16(code: u16) -> Result<Self, StatusCodeError> {
+        if code >= 100 && code <= 599 {
+            Ok(StatusCode(code))
+        } else {
+            Err(StatusCodeError::OutOfRange)
+        }
+    }
+
+    /// Try to parse a `StatusCode` from the given 3 ASCII digits, such as `b"404"`.
+    pub fn from_bytes(s: &[u8]) -> Result<Self, StatusCodeError> {
+        if s.len() != 3 {
+            return Err(StatusCodeError::BadLength);
+        }
+
+        let code = s.iter().try_fold(0, |code, &b| to_digit(b).map(|d| code * 10 + d as u16))
+            .ok_or(StatusCodeError::BadDigit)?;
+
+        StatusCode::from_u16(code)
+    }
+
+    /// Retrieve the numeric code.
+    pub const fn as_u16(&self) -> u16 {
+        self.0
+    }
+
+    /// Check if the code is informational (1xx) [RFC9110§15.2].
+    pub fn is_informational(&self) -> bool {
+        self.0 / 100 == 1
+    }
+
+    /// Check if the code indicates success (2xx) [RFC9110§15.3].
+    pub fn is_success(&self) -> bool {
+        self.0 / 100 == 2
+    }
+
+    /// Check if the code is a redirection (3xx) [RFC9110§15.4].
+    pub fn is_redirect(&self) -> bool {
+        self.0 / 100 == 3
+    }
+
+    /// Check if the code indicates a client error (4xx) [RFC9110§15.5].
+    pub fn is_client_error(&self) -> bool {
+        self.0 / 100 == 4
+    }
+
+    /// Check if the code indicates a server error (5xx) [RFC9110§15.6].
+    pub fn is_server_error(&self) -> bool {
+        self.0 / 100 == 5
+    }
+
+    /// Retrieve the canonical reason phrase for the code, if it's a well-known code.
+    pub fn canonical_reason(&self) -> Option<&'static str> {
+        Some(match self.0 {
+            100 => "Continue",
+            101 => "Switching Protocols",
+            103 => "Early Hints",
+            200 => "OK",
+            201 => "Created",
+            202 => "Accepted",
+            203 => "Non-Authoritative Information",
+            204 => "No Content",
+            205 => "Reset Content",
+            206 => "Partial Content",
+            300 => "Multiple Choices",
+            301 => "Moved Permanently",
+            302 => "Found",
+            303 => "See Other",
+            304 => "Not Modified",
+            305 => "Use Proxy",
+            307 => "Temporary Redirect",
+            308 => "Permanent Redirect",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            402 => "Payment Required",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            406 => "Not Acceptable",
+            407 => "Proxy Authentication Required",
+            408 => "Request Timeout",
+            409 => "Conflict",
+            410 => "Gone",
+            411 => "Length Required",
+            412 => "Precondition Failed",
+            413 => "Content Too Large",
+            414 => "URI Too Long",
+            415 => "Unsupported Media Type",
+            416 => "Range Not Satisfiable",
+            417 => "Expectation Failed",
+            421 => "Misdirected Request",
+            422 => "Unprocessable Content",
+            426 => "Upgrade Required",
+            428 => "Precondition Required",
+            429 => "Too Many Requests",
+            431 => "Request Header Fields Too Large",
+            500 => "Internal Server Error",
+            501 => "Not Implemented",
+            502 => "Bad Gateway",
+            503 => "Service Unavailable",
+            504 => "Gateway Timeout",
+            505 => "HTTP Version Not Supported",
+            511 => "Network Authentication Required",
+            _ => return None,
+        })
+    }
+}
+
+/// Writes the code as 3 decimal digits.
+impl fmt::Display for StatusCode {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.0)
+    }
+}
+
+impl core::str::FromStr for StatusCode {
+    type Err = StatusCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        StatusCode::from_bytes(s.as_bytes())
+    }
+}
+
+/// Errors that can occur when parsing or constructing a `StatusCode`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum StatusCodeError {
+    /// The code wasn't exactly 3 bytes long.
+    BadLength,
+    /// The code contained a byte that wasn't an ASCII decimal digit.
+    BadDigit,
+    /// The code was outside the range 100 to 599.
+    OutOfRange,
+}
+
+impl fmt::Display for StatusCodeError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(match *self {
+            StatusCodeError::BadLength => "HTTP status code isn't 3 digits long",
+            StatusCodeError::BadDigit => "HTTP status code isn't a decimal number",
+            StatusCodeError::OutOfRange => "HTTP status code isn't within 100 to 599",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StatusCodeError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_status_code() {
+        assert_eq!(StatusCode::from_bytes(b"404").unwrap().as_u16(), 404);
+        assert_eq!(StatusCode::from_bytes(b"100").unwrap().as_u16(), 100);
+        assert_eq!(StatusCode::from_bytes(b"599").unwrap().as_u16(), 599);
+        assert_eq!("200".parse(), StatusCode::from_u16(200));
+
+        assert_eq!(StatusCode::from_bytes(b"099"), Err(StatusCodeError::OutOfRange));
+        assert_eq!(StatusCode::from_bytes(b"600"), Err(StatusCodeError::OutOfRange));
+        assert_eq!(StatusCode::from_bytes(b"000"), Err(StatusCodeError::OutOfRange));
+        assert_eq!(StatusCode::from_bytes(b"20"), Err(StatusCodeError::BadLength));
+        assert_eq!(StatusCode::from_bytes(b"2000"), Err(StatusCodeError::BadLength));
+        assert_eq!(StatusCode::from_bytes(b""), Err(StatusCodeError::BadLength));
+        assert_eq!(StatusCode::from_bytes(b"2x0"), Err(StatusCodeError::BadDigit));
+        assert_eq!(StatusCode::from_bytes(b"-20"), Err(StatusCodeError::BadDigit));
+
+        assert_eq!(StatusCode::from_u16(99), Err(StatusCodeError::OutOfRange));
+        assert_eq!(StatusCode::from_u16(600), Err(StatusCodeError::OutOfRange));
+        assert!(StatusCode::from_u16(301).unwrap() < StatusCode::from_u16(404).unwrap());
+    }
+
+    #[test]
+    fn test_status_code_class() {
+        let code = |c| StatusCode::from_u16(c).unwrap();
+
+        assert!(code(100).is_informational());
+        assert!(code(199).is_informational());
+        assert!(code(200).is_success());
+        assert!(code(299).is_success());
+        assert!(code(301).is_redirect());
+        assert!(code(404).is_client_error());
+        assert!(code(500).is_server_error());
+        assert!(code(599).is_server_error());
+
+        assert!(!code(200).is_informational());
+        assert!(!code(404).is_success());
+        assert!(!code(200).is_redirect());
+        assert!(!code(500).is_client_error());
+        assert!(!code(404).is_server_error());
+    }
+
+    #[test]
+    fn test_status_code_display() {
+        let code = |c| StatusCode::from_u16(c).unwrap();
+
+        assert_eq!(code(404).to_string(), "404");
+        assert_eq!(code(100).to_string(), "100");
+
+        assert_eq!(code(200).canonical_reason(), Some("OK"));
+        assert_eq!(code(404).canonical_reason(), Some("Not Found"));
+        assert_eq!(code(505).canonical_reason(), Some("HTTP Version Not Supported"));
+        assert_eq!(code(299).canonical_reason(), None);
+        assert_eq!(code(599).canonical_reason(), None);
+    }
+}
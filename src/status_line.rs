@@ -0,0 +1,303 @@
+//! Status line parsing and formatting [RFC9112§4].
+
+use core::fmt;
+
+use grammar::is_vchar;
+use {HttpVersion, HttpVersionError, StatusCode};
+
+/// HTTP status line [RFC9112§4], borrowing its reason phrase from the parsed bytes.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct StatusLine<'a> {
+    /// Protocol version.
+    pub version: HttpVersion,
+    /// Status code, such as `404`.
+    pub code: StatusCode,
+    /// Reason phrase, such as `Not Found`, which may be empty.
+    ///
+    /// The phrase is kept as bytes since it may contain `obs-text` octets that aren't
+    /// valid UTF-8, such as Latin-1 text from older servers.
+    pub reason: &'a [u8],
+}
+
+impl<'a> StatusLine<'a> {
+    /// Try to parse a `StatusLine` from the given bytes in the form
+    /// `HTTP-version SP status-code SP [reason-phrase]`.
+    ///
+    /// The bytes must hold exactly the status line, without the trailing CRLF. An empty
+    /// reason phrase is accepted with or without its leading space.
+    pub fn from_bytes(s: &'a [u8]) -> Result<Self, StatusLineError> {
+        traced!(status_line, s, HttpVersion::from_bytes_prefix(s)
+            .map_err(StatusLineError::Version)
+            .and_then(|(version, rest)| StatusLine::from_tail(version, rest)))
+    }
+
+    /// Try to parse a `StatusLine` as in `from_bytes`, additionally accepting the `ICY`
+    /// pseudo-protocol name sent by Shoutcast servers in place of the version field, as
+    /// in `ICY 200 OK`.
+    ///
+    /// An `ICY` line is given the `icy` version, typically `HttpVersion::HTTP_10`, so
+    /// formatting the parsed line writes that version rather than `ICY`.
+    ///
+    /// Only `ICY` followed by a space is taken as the pseudo-protocol name. Any other
+    /// line, such as `ICYX 200 OK`, is parsed as in `from_bytes`.
+    pub fn from_bytes_icy(s: &'a [u8], icy: HttpVersion) -> Result<Self, StatusLineError> {
+        // Keep the space, which `from_tail` expects after the version.
+        match s.strip_prefix(b"ICY") {
+            Some(rest) if rest.starts_with(b" ") => {
+                traced!(status_line, s, StatusLine::from_tail(icy, rest))
+            },
+            _ => StatusLine::from_bytes(s),
+        }
+    }
+
+    /// Parse the rest of a status line following its already parsed version field.
+    fn from_tail(version: HttpVersion, rest: &'a [u8]) -> Result<Self, StatusLineError> {
+        let rest = match rest.split_first() {
+            Some((&b' ', rest)) => rest,
+            _ => return Err(StatusLineError::MissingSeparator),
+        };
+
+        if rest.len() < 3 {
+            return Err(StatusLineError::BadStatusCode);
+        }
+
+        let (code, rest) = rest.split_at(3);
+        let code = StatusCode::from_bytes(code).map_err(|_| StatusLineError::BadStatusCode)?;
+
+        let reason = match rest.split_first() {
+            None => &[][..],
+            Some((&b' ', reason)) => reason,
+            Some(_) => return Err(StatusLineError::BadStatusCode),
+        };
+
+        if !reason.iter().all(|&b| is_reason_char(b)) {
+            return Err(StatusLineError::BadReason);
+        }
+
+        Ok(StatusLine { version, code, reason })
+    }
+
+    /// Retrieve the reason phrase as a string, if it's valid UTF-8.
+    pub fn reason_str(&self) -> Option<&'a str> {
+        core::str::from_utf8(self.reason).ok()
+    }
+}
+
+/// Check if the given byte may appear in a reason phrase [RFC9112§4].
+fn is_reason_char(b: u8) -> bool {
+    b == b'\t' || b == b' ' || is_vchar(b) || b >= 0x80
+}
+
+/// Writes the status line in the form required by the HTTP response, without the
+/// trailing CRLF.
+///
+/// Each invalid UTF-8 sequence in the reason phrase is written as U+FFFD, as in
+/// `String::from_utf8_lossy`.
+impl<'a> fmt::Display for StatusLine<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{} {} ", self.version, self.code)?;
+
+        let mut rest = self.reason;
+
+        loop {
+            match core::str::from_utf8(rest) {
+                Ok(s) => return fmt.write_str(s),
+                Err(err) => {
+                    let (valid, bad) = rest.split_at(err.valid_up_to());
+                    // Always valid, since it ends where the error starts.
+                    fmt.write_str(core::str::from_utf8(valid).map_err(|_| fmt::Error)?)?;
+                    fmt.write_str("\u{FFFD}")?;
+                    rest = &bad[err.error_len().unwrap_or(bad.len())..];
+                },
+            }
+        }
+    }
+}
+
+/// Errors that can occur when parsing a `StatusLine`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum StatusLineError {
+    /// The version wasn't followed by a space.
+    MissingSeparator,
+    /// The status code wasn't three digits within 100 to 599 followed by a space or the
+    /// end of the line.
+    BadStatusCode,
+    /// The reason phrase contained a control character.
+    BadReason,
+    /// The version field was malformed.
+    Version(HttpVersionError),
+}
+
+impl From<HttpVersionError> for StatusLineError {
+    fn from(err: HttpVersionError) -> Self {
+        StatusLineError::Version(err)
+    }
+}
+
+impl fmt::Display for StatusLineError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::StatusLineError::*;
+
+        match *self {
+            MissingSeparator => fmt.write_str("status line is missing a space separator"),
+            BadStatusCode => fmt.write_str("status line has an invalid status code"),
+            BadReason => fmt.write_str("status line has an invalid reason phrase"),
+            Version(ref err) => fmt::Display::fmt(err, fmt),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StatusLineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            StatusLineError::Version(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_status_line() {
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1 200 OK"), Ok(StatusLine {
+            version: HttpVersion::HTTP_11,
+            code: StatusCode::from_u16(200).unwrap(),
+            reason: b"OK",
+        }));
+
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.0 404 Not Found"), Ok(StatusLine {
+            version: HttpVersion::HTTP_10,
+            code: StatusCode::from_u16(404).unwrap(),
+            reason: b"Not Found",
+        }));
+
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1 204 "), Ok(StatusLine {
+            version: HttpVersion::HTTP_11,
+            code: StatusCode::from_u16(204).unwrap(),
+            reason: b"",
+        }));
+
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1 204"), Ok(StatusLine {
+            version: HttpVersion::HTTP_11,
+            code: StatusCode::from_u16(204).unwrap(),
+            reason: b"",
+        }));
+
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1 500 Oops \t it broke").unwrap().reason,
+                   b"Oops \t it broke");
+        assert_eq!(StatusLine::from_bytes("HTTP/1.1 200 Très bien".as_bytes()).unwrap()
+                       .reason_str(),
+                   Some("Très bien"));
+
+        let line = StatusLine::from_bytes(b"HTTP/1.1 200 Tr\xe8s bien").unwrap();
+        assert_eq!(line.reason, b"Tr\xe8s bien");
+        assert_eq!(line.reason_str(), None);
+
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1"), Err(StatusLineError::MissingSeparator));
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1_200 OK"),
+                   Err(StatusLineError::MissingSeparator));
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1 20 OK"), Err(StatusLineError::BadStatusCode));
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1 20"), Err(StatusLineError::BadStatusCode));
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1 2000 OK"),
+                   Err(StatusLineError::BadStatusCode));
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1 099 OK"),
+                   Err(StatusLineError::BadStatusCode));
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1 600 OK"),
+                   Err(StatusLineError::BadStatusCode));
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1 2x0 OK"),
+                   Err(StatusLineError::BadStatusCode));
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1  200 OK"),
+                   Err(StatusLineError::BadStatusCode));
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1 200 OK\r\n"),
+                   Err(StatusLineError::BadReason));
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1 200 \xff").unwrap().reason, b"\xff");
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.1 200 \x7f"), Err(StatusLineError::BadReason));
+        assert_eq!(StatusLine::from_bytes(b"HTTP/1.10 200 OK"),
+                   Err(StatusLineError::Version(HttpVersionError::BadLength)));
+        assert_eq!(StatusLine::from_bytes(b"ICY 200 OK"),
+                   Err(StatusLineError::Version(HttpVersionError::BadPrefix)));
+    }
+
+    #[test]
+    fn test_status_line_icy() {
+        let v10 = HttpVersion::HTTP_10;
+
+        assert_eq!(StatusLine::from_bytes_icy(b"ICY 200 OK", v10), Ok(StatusLine {
+            version: v10,
+            code: StatusCode::from_u16(200).unwrap(),
+            reason: b"OK",
+        }));
+        assert_eq!(StatusLine::from_bytes_icy(b"ICY 404", HttpVersion::HTTP_11).unwrap().version,
+                   HttpVersion::HTTP_11);
+        assert_eq!(StatusLine::from_bytes_icy(b"HTTP/1.1 200 OK", v10).unwrap().version,
+                   HttpVersion::HTTP_11);
+        assert_eq!(StatusLine::from_bytes_icy(b"ICY 200 OK", v10).unwrap().to_string(),
+                   "HTTP/1.0 200 OK");
+
+        assert_eq!(StatusLine::from_bytes_icy(b"ICY", v10),
+                   Err(StatusLineError::Version(HttpVersionError::BadPrefix)));
+        assert_eq!(StatusLine::from_bytes_icy(b"ICY/1.0 200 OK", v10),
+                   Err(StatusLineError::Version(HttpVersionError::BadPrefix)));
+        assert_eq!(StatusLine::from_bytes_icy(b"ICYX 200 OK", v10),
+                   Err(StatusLineError::Version(HttpVersionError::BadPrefix)));
+        assert_eq!(StatusLine::from_bytes_icy(b"ICY  200 OK", v10),
+                   Err(StatusLineError::BadStatusCode));
+        assert_eq!(StatusLine::from_bytes_icy(b"ICY 20 OK", v10),
+                   Err(StatusLineError::BadStatusCode));
+        assert_eq!(StatusLine::from_bytes_icy(b"icy 200 OK", v10),
+                   Err(StatusLineError::Version(HttpVersionError::BadPrefix)));
+    }
+
+    #[test]
+    fn test_status_line_display() {
+        let line = StatusLine {
+            version: HttpVersion::HTTP_11,
+            code: StatusCode::from_u16(404).unwrap(),
+            reason: b"Not Found",
+        };
+
+        assert_eq!(line.to_string(), "HTTP/1.1 404 Not Found");
+
+        let line = StatusLine {
+            version: HttpVersion::HTTP_10,
+            code: StatusCode::from_u16(204).unwrap(),
+            reason: b"",
+        };
+
+        assert_eq!(line.to_string(), "HTTP/1.0 204 ");
+
+        let mut buf = [b'#'; 17];
+        write!(&mut buf[..], "{}\r\n", StatusLine::from_bytes(b"HTTP/1.1 200 OK").unwrap())
+            .unwrap();
+        assert_eq!(&buf[..], b"HTTP/1.1 200 OK\r\n");
+
+        let line = b"HTTP/1.1 503 Service Unavailable";
+        assert_eq!(StatusLine::from_bytes(line).unwrap().to_string().as_bytes(), &line[..]);
+
+        let line = StatusLine::from_bytes(b"HTTP/1.1 200 Tr\xe8s \xff\xfebien").unwrap();
+        assert_eq!(line.to_string(), "HTTP/1.1 200 Tr\u{FFFD}s \u{FFFD}\u{FFFD}bien");
+
+        // Formatting isn't a round trip for invalid UTF-8, which is replaced as in
+        // `from_utf8_lossy`.
+        for &reason in [&b"\x80"[..], b"\xe2\x82", b"ok \xc3(", b"\xf0\x9f\x92"].iter() {
+            let mut s = b"HTTP/1.1 200 ".to_vec();
+            s.extend_from_slice(reason);
+
+            let line = StatusLine::from_bytes(&s).unwrap();
+            let formatted = line.to_string();
+            let parsed = StatusLine::from_bytes(formatted.as_bytes()).unwrap();
+
+            assert_ne!(parsed, line);
+            assert_eq!(parsed.reason, String::from_utf8_lossy(reason).as_bytes());
+            assert_eq!(parsed.reason_str(), Some(&formatted["HTTP/1.1 200 ".len()..]));
+        }
+        assert_eq!(StatusLine::from_bytes("HTTP/1.1 200 Très".as_bytes()).unwrap().to_string(),
+                   "HTTP/1.1 200 Très");
+    }
+}
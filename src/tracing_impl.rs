@@ -0,0 +1,269 @@
+//! Diagnostic `tracing` events from the parsing entry points.
+//!
+//! Successful parses emit a `trace` event and failures a `debug` event, all with the
+//! `uhttp_version` target. Each event records the input length, and failures record the
+//! reason along with, for malformed version fields, the offset of the first offending
+//! byte from the start of the input.
+
+use core::fmt;
+
+use {HttpVersion, HttpVersionError, RequestLine, RequestLineError, StatusLine,
+     StatusLineError, is_multi_digit_major};
+
+/// Report the result of parsing the given version field.
+pub fn version(s: &[u8], res: &Result<HttpVersion, HttpVersionError>) {
+    match *res {
+        Ok(ver) => tracing::trace!(
+            target: "uhttp_version",
+            len = s.len(),
+            major = ver.major,
+            minor = ver.minor,
+            "parsed HTTP version field"
+        ),
+        Err(err) => tracing::debug!(
+            target: "uhttp_version",
+            len = s.len(),
+            offset = error_offset(s, err),
+            reason = %err,
+            "malformed HTTP version field"
+        ),
+    }
+}
+
+/// Report the result of parsing the given request line.
+pub fn request_line(s: &[u8], res: &Result<RequestLine, RequestLineError>) {
+    let offset = match *res {
+        // The version field follows the second space, as split by the parser.
+        Err(RequestLineError::Version(err)) => {
+            let start = s.iter().enumerate().filter(|&(_, &b)| b == b' ').nth(1)
+                .map(|(idx, _)| idx + 1);
+
+            start.and_then(|start| field_offset(s, start, s.len(), err))
+        },
+        _ => None,
+    };
+
+    line("request", s, res.as_ref().map(|line| line.version), offset);
+}
+
+/// Report the result of parsing the given status line.
+pub fn status_line(s: &[u8], res: &Result<StatusLine, StatusLineError>) {
+    let offset = match *res {
+        // The version field is parsed from the first 8 bytes.
+        Err(StatusLineError::Version(err)) => field_offset(s, 0, s.len().min(8), err),
+        _ => None,
+    };
+
+    line("status", s, res.as_ref().map(|line| line.version), offset);
+}
+
+/// Find the offset from the start of the given line of the first byte that made the
+/// version field within the given bounds malformed.
+fn field_offset(s: &[u8], start: usize, end: usize, err: HttpVersionError)
+    -> Option<usize>
+{
+    s.get(start..end).and_then(|field| error_offset(field, err)).map(|off| start + off)
+}
+
+/// Report the result of parsing the given start line of the given kind, with the
+/// offset of any malformed version field.
+fn line<E: fmt::Display>(kind: &'static str, s: &[u8], res: Result<HttpVersion, &E>,
+                         offset: Option<usize>)
+{
+    match res {
+        Ok(ver) => tracing::trace!(
+            target: "uhttp_version",
+            line = kind,
+            len = s.len(),
+            major = ver.major,
+            minor = ver.minor,
+            "parsed HTTP start line"
+        ),
+        Err(err) => tracing::debug!(
+            target: "uhttp_version",
+            line = kind,
+            len = s.len(),
+            offset,
+            reason = %err,
+            "malformed HTTP start line"
+        ),
+    }
+}
+
+/// Find the offset of the first byte that made the given field malformed, if the error
+/// is confined to one.
+///
+/// An offset equal to the field length means the field ended too early.
+fn error_offset(s: &[u8], err: HttpVersionError) -> Option<usize> {
+    match err {
+        HttpVersionError::BadPrefix => {
+            Some(b"HTTP/".iter().zip(s).position(|(a, b)| a != b).unwrap_or(s.len()))
+        },
+        HttpVersionError::BadLength if is_multi_digit_major(s) => Some(6),
+        HttpVersionError::BadLength => Some(s.len().min(8)),
+        // Only reported for an 8-byte field starting with `HTTP/`, so the offending byte
+        // is always the one in place of the `.`.
+        HttpVersionError::BadSeparator => Some(6),
+        HttpVersionError::BadDigit => match s.get(5) {
+            Some(b) if b.is_ascii_digit() => Some(7),
+            _ => Some(5),
+        },
+        _ => None,
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use std::fmt::Write;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Subscriber recording each event as a line of its level and fields.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, _: &Record) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event) {
+            let mut line = format!("{} {}", event.metadata().level(), event.metadata().target());
+            event.record(&mut FieldWriter(&mut line));
+            self.0.lock().unwrap().push(line);
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    /// Visitor appending each field to a line.
+    struct FieldWriter<'a>(&'a mut String);
+
+    impl<'a> Visit for FieldWriter<'a> {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            write!(self.0, " {}={:?}", field.name(), value).unwrap();
+        }
+    }
+
+    /// Collect the events emitted while running the given function.
+    fn capture<F: FnOnce()>(f: F) -> Vec<String> {
+        let rec = Recorder::default();
+        tracing::subscriber::with_default(rec.clone(), f);
+
+        let mut events = Vec::new();
+        events.append(&mut rec.0.lock().unwrap());
+        events
+    }
+
+    #[test]
+    fn test_version_events() {
+        assert_eq!(capture(|| { HttpVersion::from_bytes(b"HTTP/1.1").unwrap(); }), [
+            "TRACE uhttp_version message=parsed HTTP version field len=8 major=1 minor=1",
+        ]);
+
+        assert_eq!(capture(|| { HttpVersion::from_bytes(b"HTTP/1-1").unwrap_err(); }), [
+            "DEBUG uhttp_version message=malformed HTTP version field len=8 offset=6 \
+             reason=HTTP version parts aren't separated by '.'",
+        ]);
+    }
+
+    #[test]
+    fn test_fallback_silent() {
+        assert!(capture(|| {
+            assert_eq!(HttpVersion::from_bytes_or(b"HTTP/1-1", HttpVersion::HTTP_11),
+                       HttpVersion::HTTP_11);
+            assert_eq!(HttpVersion::from_bytes_or(b"HTTP/2.0", HttpVersion::HTTP_11),
+                       HttpVersion::HTTP_2);
+        }).is_empty());
+    }
+
+    #[test]
+    fn test_line_events() {
+        let events = capture(|| { RequestLine::from_bytes(b"GE(T / HTTP/1.1").unwrap_err(); });
+        assert_eq!(events, [
+            "DEBUG uhttp_version message=malformed HTTP start line line=\"request\" len=15 \
+             reason=request line has an invalid method",
+        ]);
+
+        let events = capture(|| { StatusLine::from_bytes(b"HTTP/1.0 200 OK").unwrap(); });
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1], "TRACE uhttp_version message=parsed HTTP start line \
+                               line=\"status\" len=15 major=1 minor=0");
+
+        let events = capture(|| {
+            StatusLine::from_bytes_icy(b"ICY 200 OK", HttpVersion::HTTP_10).unwrap();
+        });
+        assert_eq!(events, ["TRACE uhttp_version message=parsed HTTP start line \
+                             line=\"status\" len=10 major=1 minor=0"]);
+    }
+
+    #[test]
+    fn test_line_offsets() {
+        /// Capture the offset recorded when parsing the given line.
+        fn offset<F: FnOnce()>(f: F) -> Option<String> {
+            capture(f).pop().and_then(|event| {
+                event.split(' ').find(|field| field.starts_with("offset="))
+                    .map(|field| field["offset=".len()..].to_string())
+            })
+        }
+
+        for &(s, expected) in [
+            (&b"GET / HTTP/1-1"[..], Some("12")),
+            (b"GET / HTTP/x.1", Some("11")),
+            (b"GET /index.html HTTX/1.1", Some("19")),
+            (b"GET / HTTP/1.10", Some("14")),
+            (b"GET / HTTP/1.", Some("13")),
+            (b"GE(T / HTTP/1.1", None),
+            (b"GET / HTTP/1.1", None),
+        ].iter() {
+            assert_eq!(offset(|| { let _ = RequestLine::from_bytes(s); }).as_ref().map(|s| &s[..]),
+                       expected, "{:?}", s);
+        }
+
+        for &(s, expected) in [
+            (&b"HTTX/1.1 200 OK"[..], Some("3")),
+            (b"HTTP/1,1 200 OK", Some("6")),
+            (b"HTTP/1.x 200 OK", Some("7")),
+            (b"HTTP/1.12 200 OK", Some("8")),
+            (b"HTTP/10.1 200 OK", Some("6")),
+            (b"HTTP/1.", Some("7")),
+            (b"HTTP/1.1 2x0 OK", None),
+        ].iter() {
+            assert_eq!(offset(|| { let _ = StatusLine::from_bytes(s); }).as_ref().map(|s| &s[..]),
+                       expected, "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_error_offset() {
+        for &(s, err, offset) in [
+            (&b"HTTX/1.1"[..], HttpVersionError::BadPrefix, Some(3)),
+            (b"HTT", HttpVersionError::BadPrefix, Some(3)),
+            (b"", HttpVersionError::BadPrefix, Some(0)),
+            (b"HTTP/1.10", HttpVersionError::BadLength, Some(8)),
+            (b"HTTP/1.", HttpVersionError::BadLength, Some(7)),
+            (b"HTTP/10.1", HttpVersionError::BadLength, Some(6)),
+            (b"HTTP/1,1", HttpVersionError::BadSeparator, Some(6)),
+            (b"HTTP/x.1", HttpVersionError::BadDigit, Some(5)),
+            (b"HTTP/1.x", HttpVersionError::BadDigit, Some(7)),
+        ].iter() {
+            assert_eq!(HttpVersion::from_bytes(s), Err(err));
+            assert_eq!(error_offset(s, err), offset);
+        }
+
+        assert_eq!(error_offset(b"HTTP/1.1", HttpVersionError::TooNew), None);
+    }
+}
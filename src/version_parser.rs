@@ -0,0 +1,314 @@
+//! Incremental version field parsing.
+
+use core::iter;
+
+use {HttpVersion, HttpVersionError};
+
+/// Incremental parser for the `HTTP/x.y` version field, for input that arrives in
+/// pieces, such as from a non-blocking socket.
+///
+/// Bytes of the `HTTP/` name are checked as soon as they're pushed, so input that isn't a
+/// version field fails without waiting for the whole field. The version number is
+/// checked once the whole field has arrived, so every error is the same as `from_bytes`
+/// gives for the field. After returning a version or an error, the parser starts over
+/// with the next push.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Default)]
+pub struct VersionParser {
+    /// Bytes of the field received so far.
+    buf: [u8; 8],
+    /// Number of bytes in `buf`.
+    len: usize,
+}
+
+/// Result of pushing bytes into a `VersionParser`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum ParseStatus {
+    /// All the pushed bytes were consumed without completing the field.
+    NeedMore,
+    /// The field is complete.
+    Done {
+        /// Parsed version.
+        version: HttpVersion,
+        /// Number of bytes consumed from the last push, with any remaining bytes
+        /// belonging to whatever follows the version.
+        consumed: usize,
+    },
+}
+
+impl VersionParser {
+    /// Create a new `VersionParser` expecting the start of a version field.
+    pub fn new() -> Self {
+        VersionParser::default()
+    }
+
+    /// Feed the given bytes into the parser.
+    ///
+    /// Like `from_bytes_prefix`, this only consumes the 8 bytes of the field, but it
+    /// can't check that the field isn't followed by another digit, which is left to the
+    /// caller.
+    pub fn push(&mut self, s: &[u8]) -> Result<ParseStatus, HttpVersionError> {
+        for (idx, &b) in s.iter().enumerate() {
+            if !is_name_byte(self.len, b) {
+                self.reset();
+                return Err(HttpVersionError::BadPrefix);
+            }
+
+            // Always has room, since the parser starts over once the field is full.
+            if let Some(slot) = self.buf.get_mut(self.len) {
+                *slot = b;
+            }
+
+            self.len += 1;
+
+            if self.len == self.buf.len() {
+                let version = HttpVersion::from_bytes_prefix(&self.buf).map(|(ver, _)| ver);
+                self.reset();
+
+                return version.map(|version| ParseStatus::Done {
+                    version,
+                    consumed: idx + 1,
+                });
+            }
+        }
+
+        Ok(ParseStatus::NeedMore)
+    }
+
+    /// Retrieve the number of bytes still needed to complete the field.
+    ///
+    /// Pushing at most this many bytes consumes all of them, which lets a reader avoid
+    /// reading past the field.
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.len
+    }
+
+    /// Signal the end of the input, returning the error for the incomplete field and
+    /// starting over.
+    ///
+    /// The error is the same as `from_bytes` would give for the bytes pushed so far.
+    pub fn finish(&mut self) -> HttpVersionError {
+        let err = if self.len < b"HTTP/".len() {
+            HttpVersionError::BadPrefix
+        } else {
+            HttpVersionError::BadLength
+        };
+
+        self.reset();
+        err
+    }
+
+    /// Discard any partial field and start over.
+    pub fn reset(&mut self) {
+        *self = VersionParser::new();
+    }
+}
+
+impl HttpVersion {
+    /// Try to parse an `HttpVersion` from the start of the given sequence of byte
+    /// slices, such as the two halves of a ring buffer, without copying them together.
+    ///
+    /// This behaves like `from_bytes_prefix` on the concatenated slices, with the field
+    /// allowed to straddle any number of slice boundaries.
+    pub fn from_chunks<'a, I>(chunks: I) -> Result<Self, HttpVersionError>
+        where I: IntoIterator<Item = &'a [u8]>
+    {
+        let mut parser = VersionParser::new();
+        let mut chunks = chunks.into_iter();
+
+        let (ver, rest) = loop {
+            let chunk = match chunks.next() {
+                Some(chunk) => chunk,
+                None => return Err(parser.finish()),
+            };
+
+            if let ParseStatus::Done { version, consumed } = parser.push(chunk)? {
+                break (version, &chunk[consumed..]);
+            }
+        };
+
+        // The byte following the field may be in any later slice.
+        match iter::once(rest).chain(chunks).flat_map(|chunk| chunk.first()).next() {
+            Some(b) if b.is_ascii_digit() => Err(HttpVersionError::BadLength),
+            _ => Ok(ver),
+        }
+    }
+}
+
+/// Check if the given byte may appear at the given position within the version field,
+/// considering only the `HTTP/` name.
+///
+/// Errors in the version number are left to `from_bytes`, since which one it reports
+/// depends on the bytes that follow.
+fn is_name_byte(pos: usize, b: u8) -> bool {
+    match b"HTTP/".get(pos) {
+        Some(&c) => b == c,
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_version_parser() {
+        let mut p = VersionParser::new();
+        assert_eq!(p.push(b"HTTP/1.1\r\n"), Ok(ParseStatus::Done {
+            version: HttpVersion::HTTP_11,
+            consumed: 8,
+        }));
+
+        let mut p = VersionParser::new();
+        assert_eq!(p.push(b"HT"), Ok(ParseStatus::NeedMore));
+        assert_eq!(p.push(b""), Ok(ParseStatus::NeedMore));
+        assert_eq!(p.push(b"TP/1"), Ok(ParseStatus::NeedMore));
+        assert_eq!(p.push(b"."), Ok(ParseStatus::NeedMore));
+        assert_eq!(p.push(b"0 200 OK"), Ok(ParseStatus::Done {
+            version: HttpVersion::HTTP_10,
+            consumed: 1,
+        }));
+
+        assert_eq!(p.push(b"HTTP/2.0"), Ok(ParseStatus::Done {
+            version: HttpVersion::HTTP_2,
+            consumed: 8,
+        }));
+
+        for split in 0..=8 {
+            let (a, b) = b"HTTP/4.2".split_at(split);
+            let mut p = VersionParser::new();
+
+            let status = match p.push(a) {
+                Ok(ParseStatus::NeedMore) => p.push(b),
+                status => status,
+            };
+
+            assert_eq!(status, Ok(ParseStatus::Done {
+                version: HttpVersion::from_parts(4, 2),
+                consumed: if split == 8 { 8 } else { 8 - split },
+            }));
+        }
+
+        let mut p = VersionParser::new();
+        for &b in b"HTTP/0." {
+            assert_eq!(p.push(&[b]), Ok(ParseStatus::NeedMore));
+        }
+        assert_eq!(p.push(b"9"), Ok(ParseStatus::Done {
+            version: HttpVersion::HTTP_09,
+            consumed: 1,
+        }));
+    }
+
+    #[test]
+    fn test_remaining() {
+        let mut p = VersionParser::new();
+        assert_eq!(p.remaining(), 8);
+        assert_eq!(p.push(b"HTTP"), Ok(ParseStatus::NeedMore));
+        assert_eq!(p.remaining(), 4);
+        assert_eq!(p.push(b"/1."), Ok(ParseStatus::NeedMore));
+        assert_eq!(p.remaining(), 1);
+        assert_eq!(p.push(b"1"), Ok(ParseStatus::Done {
+            version: HttpVersion::HTTP_11,
+            consumed: 1,
+        }));
+        assert_eq!(p.remaining(), 8);
+        assert_eq!(p.push(b"HTTP/1-"), Ok(ParseStatus::NeedMore));
+        assert_eq!(p.remaining(), 1);
+        assert_eq!(p.push(b"1"), Err(HttpVersionError::BadSeparator));
+        assert_eq!(p.remaining(), 8);
+    }
+
+    #[test]
+    fn test_finish() {
+        let mut p = VersionParser::new();
+        assert_eq!(p.finish(), HttpVersionError::BadPrefix);
+        assert_eq!(p.push(b"HTTP"), Ok(ParseStatus::NeedMore));
+        assert_eq!(p.finish(), HttpVersionError::BadPrefix);
+        assert_eq!(p.push(b"HTTP/1."), Ok(ParseStatus::NeedMore));
+        assert_eq!(p.finish(), HttpVersionError::BadLength);
+        assert_eq!(p.push(b"HTTP/1.1"), Ok(ParseStatus::Done {
+            version: HttpVersion::HTTP_11,
+            consumed: 8,
+        }));
+    }
+
+    #[test]
+    fn test_from_chunks() {
+        let line = b"HTTP/1.1 200 OK";
+
+        for split in 0..=line.len() {
+            let (a, b) = line.split_at(split);
+            assert_eq!(HttpVersion::from_chunks(vec![a, b]), Ok(HttpVersion::HTTP_11));
+        }
+
+        let chunks: Vec<&[u8]> = b"HTTP/4.2".iter().map(core::slice::from_ref).collect();
+        assert_eq!(HttpVersion::from_chunks(chunks), Ok(HttpVersion::from_parts(4, 2)));
+
+        assert_eq!(HttpVersion::from_chunks(vec![&b""[..], b"HTTP/1.0", b""]),
+                   Ok(HttpVersion::HTTP_10));
+        assert_eq!(HttpVersion::from_chunks(vec![&b"HTTP/1.0"[..], b"", b"x"]),
+                   Ok(HttpVersion::HTTP_10));
+
+        for split in 0..=9 {
+            let (a, b) = b"HTTP/1.10".split_at(split);
+            assert_eq!(HttpVersion::from_chunks(vec![a, b]), Err(HttpVersionError::BadLength));
+        }
+
+        assert_eq!(HttpVersion::from_chunks(vec![&b"HTTP/1.0"[..], b"", b"1"]),
+                   Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_chunks(Vec::new()), Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_chunks(vec![&b"HT"[..], b"TP"]),
+                   Err(HttpVersionError::BadPrefix));
+        assert_eq!(HttpVersion::from_chunks(vec![&b"HTTP/"[..], b"1"]),
+                   Err(HttpVersionError::BadLength));
+        assert_eq!(HttpVersion::from_chunks(vec![&b"HTTP/"[..], b"1-1"]),
+                   Err(HttpVersionError::BadSeparator));
+        assert_eq!(HttpVersion::from_chunks(vec![&b"http"[..], b"/1.1"]),
+                   Err(HttpVersionError::BadPrefix));
+
+        for input in [&b"HTTP"[..], b"HTTP/2", b"HTTP/1.1", b"HTTP/1.12", b"HTTP/1,1"].iter() {
+            assert_eq!(HttpVersion::from_chunks(vec![*input]),
+                       HttpVersion::from_bytes_prefix(input).map(|(ver, _)| ver));
+        }
+    }
+
+    #[test]
+    fn test_version_parser_errors() {
+        // Every single-byte change of a field, and every truncation of those, gives the
+        // same result byte by byte as parsing the whole input at once.
+        for pos in 0..8 {
+            for b in 0..=255 {
+                let mut field = *b"HTTP/1.1";
+                field[pos] = b;
+
+                for len in 0..=field.len() {
+                    let input = &field[..len];
+                    let chunks: Vec<&[u8]> = input.iter().map(core::slice::from_ref).collect();
+
+                    assert_eq!(HttpVersion::from_chunks(chunks),
+                               HttpVersion::from_bytes_prefix(input).map(|(ver, _)| ver),
+                               "{:?}", input);
+                }
+            }
+        }
+
+        for input in [&b"HTTP/x-1"[..], b"HTTP/x.@", b"HTTP/1-", b"HTTP/-.1", b"HTTx/"].iter() {
+            assert_eq!(HttpVersion::from_chunks(vec![*input]),
+                       HttpVersion::from_bytes_prefix(input).map(|(ver, _)| ver));
+        }
+
+        let mut p = VersionParser::new();
+        assert_eq!(p.push(b"X"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(p.push(b"HTTp"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(p.push(b"HTT"), Ok(ParseStatus::NeedMore));
+        assert_eq!(p.push(b"P/x-1"), Err(HttpVersionError::BadSeparator));
+
+        let mut p = VersionParser::new();
+        assert_eq!(p.push(b"HTTP/1."), Ok(ParseStatus::NeedMore));
+        p.reset();
+        assert_eq!(p.push(b"1"), Err(HttpVersionError::BadPrefix));
+        assert_eq!(p.push(b"HTTP/1.1"), Ok(ParseStatus::Done {
+            version: HttpVersion::HTTP_11,
+            consumed: 8,
+        }));
+    }
+}
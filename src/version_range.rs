@@ -0,0 +1,304 @@
+//! Version requirements, such as a minimum accepted version.
+
+use core::fmt;
+
+use {HttpVersion, HttpVersionError, parse_number};
+
+/// Inclusive range of versions, for expressing requirements like "at least HTTP/1.1" or
+/// "any HTTP/1.x".
+///
+/// The compact string form is one of:
+///
+/// - `*` for any version
+/// - `1.1` for exactly HTTP/1.1
+/// - `>=1.1` for HTTP/1.1 or later
+/// - `<=1.1` for HTTP/1.1 or earlier
+/// - `1.*` for any HTTP/1.x
+/// - `1.0-2.0` for HTTP/1.0 through HTTP/2.0
+///
+/// Versions compare as in `HttpVersion`'s `Ord`, so the range `1.0-2.0` contains
+/// HTTP/1.5 but not HTTP/2.1.
+///
+/// The string form round-trips only for ranges whose bounds are valid versions. A range
+/// such as `at_least(HttpVersion::from_parts(12, 34))` is written as `>=12.34`, which
+/// `from_bytes` rejects.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct VersionRange {
+    /// Lowest contained version, or `None` if unbounded.
+    min: Option<HttpVersion>,
+    /// Highest contained version, or `None` if unbounded.
+    max: Option<HttpVersion>,
+    /// Whether the range is `x.*`, which also contains the multi-digit minor parts of
+    /// its major part beyond `max`.
+    any_minor: bool,
+}
+
+impl VersionRange {
+    /// Range containing every version.
+    pub const ANY: VersionRange = VersionRange { min: None, max: None, any_minor: false };
+
+    /// Create a range containing the given version and all later versions.
+    pub const fn at_least(min: HttpVersion) -> Self {
+        VersionRange { min: Some(min), max: None, any_minor: false }
+    }
+
+    /// Create a range containing the given version and all earlier versions.
+    pub const fn at_most(max: HttpVersion) -> Self {
+        VersionRange { min: None, max: Some(max), any_minor: false }
+    }
+
+    /// Create a range containing only the given version.
+    pub const fn exactly(ver: HttpVersion) -> Self {
+        VersionRange { min: Some(ver), max: Some(ver), any_minor: false }
+    }
+
+    /// Create a range containing every version with the given single-digit major part,
+    /// whatever its minor part.
+    ///
+    /// The range is bounded by `x.0` and `x.9`, but also contains versions built with
+    /// multi-digit minor parts. A major part above 9 is clamped to 9, since only
+    /// single-digit majors have a compact string form.
+    pub const fn major(major: u8) -> Self {
+        let major = if major > 9 { 9 } else { major };
+
+        VersionRange {
+            min: Some(HttpVersion::from_parts(major, 0)),
+            max: Some(HttpVersion::from_parts(major, 9)),
+            any_minor: true,
+        }
+    }
+
+    /// Create a range containing the versions from `min` through `max`, failing if `min`
+    /// is greater than `max`.
+    pub fn between(min: HttpVersion, max: HttpVersion) -> Result<Self, VersionRangeError> {
+        if min <= max {
+            Ok(VersionRange { min: Some(min), max: Some(max), any_minor: false })
+        } else {
+            Err(VersionRangeError::Reversed)
+        }
+    }
+
+    /// Try to parse a range from its compact string form.
+    pub fn from_bytes(s: &[u8]) -> Result<Self, VersionRangeError> {
+        if s == b"*" {
+            return Ok(VersionRange::ANY);
+        }
+
+        if s.starts_with(b">=") {
+            return Ok(VersionRange::at_least(parse_number(&s[2..])?));
+        }
+
+        if s.starts_with(b"<=") {
+            return Ok(VersionRange::at_most(parse_number(&s[2..])?));
+        }
+
+        if let Some(idx) = s.iter().position(|&b| b == b'-') {
+            return VersionRange::between(parse_number(&s[..idx])?,
+                                         parse_number(&s[idx + 1..])?);
+        }
+
+        match *s {
+            [major, b'.', b'*'] if major.is_ascii_digit() => {
+                Ok(VersionRange::major(major - b'0'))
+            },
+            _ => Ok(VersionRange::exactly(parse_number(s)?)),
+        }
+    }
+
+    /// Retrieve the lowest version in the range, if it's bounded below.
+    pub fn min(&self) -> Option<HttpVersion> {
+        self.min
+    }
+
+    /// Retrieve the highest version in the range, if it's bounded above.
+    pub fn max(&self) -> Option<HttpVersion> {
+        self.max
+    }
+
+    /// Check if the range contains the given version.
+    pub fn contains(&self, ver: HttpVersion) -> bool {
+        if let (true, Some(min)) = (self.any_minor, self.min) {
+            return ver.major == min.major;
+        }
+
+        let above_min = match self.min {
+            Some(min) => ver >= min,
+            None => true,
+        };
+
+        let below_max = match self.max {
+            Some(max) => ver <= max,
+            None => true,
+        };
+
+        above_min && below_max
+    }
+}
+
+/// Writes the range in its compact string form, such as `>=1.1`.
+impl fmt::Display for VersionRange {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match (self.min, self.max) {
+            (Some(min), _) if self.any_minor => write!(fmt, "{}.*", min.major),
+            (None, None) => fmt.write_str("*"),
+            (Some(min), None) => write!(fmt, ">={:#}", min),
+            (None, Some(max)) => write!(fmt, "<={:#}", max),
+            (Some(min), Some(max)) if min == max => write!(fmt, "{:#}", min),
+            (Some(min), Some(max)) => write!(fmt, "{:#}-{:#}", min, max),
+        }
+    }
+}
+
+impl core::str::FromStr for VersionRange {
+    type Err = VersionRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        VersionRange::from_bytes(s.as_bytes())
+    }
+}
+
+/// Errors that can occur when creating or parsing a `VersionRange`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum VersionRangeError {
+    /// The lower bound was greater than the upper bound.
+    Reversed,
+    /// A version in the range was malformed.
+    Version(HttpVersionError),
+}
+
+impl From<HttpVersionError> for VersionRangeError {
+    fn from(err: HttpVersionError) -> Self {
+        VersionRangeError::Version(err)
+    }
+}
+
+impl fmt::Display for VersionRangeError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VersionRangeError::Reversed => fmt.write_str("version range bounds are reversed"),
+            VersionRangeError::Version(ref err) => fmt::Display::fmt(err, fmt),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VersionRangeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            VersionRangeError::Version(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        let v09 = HttpVersion::HTTP_09;
+        let v10 = HttpVersion::HTTP_10;
+        let v11 = HttpVersion::HTTP_11;
+        let v2 = HttpVersion::HTTP_2;
+
+        assert!(VersionRange::ANY.contains(v09));
+        assert!(VersionRange::ANY.contains(HttpVersion { major: 12, minor: 34 }));
+
+        let range = VersionRange::at_least(v11);
+        assert!(!range.contains(v10));
+        assert!(range.contains(v11));
+        assert!(range.contains(v2));
+
+        let range = VersionRange::at_most(v10);
+        assert!(range.contains(v09));
+        assert!(range.contains(v10));
+        assert!(!range.contains(v11));
+
+        let range = VersionRange::exactly(v11);
+        assert!(range.contains(v11));
+        assert!(!range.contains(v10));
+        assert!(!range.contains(HttpVersion::from_parts(1, 2)));
+
+        let range = VersionRange::major(1);
+        assert!(!range.contains(v09));
+        assert!(range.contains(v10));
+        assert!(range.contains(HttpVersion::from_parts(1, 9)));
+        assert!(range.contains(HttpVersion::from_parts(1, 10)));
+        assert!(range.contains(HttpVersion::from_parts(1, 255)));
+        assert!(!range.contains(HttpVersion::from_parts(2, 255)));
+        assert_eq!(range.min(), Some(v10));
+        assert_eq!(range.max(), Some(HttpVersion::from_parts(1, 9)));
+        assert!(range.max().unwrap().is_valid());
+        assert_ne!(range, VersionRange::between(v10, HttpVersion::from_parts(1, 9)).unwrap());
+        assert!(!range.contains(v2));
+
+        assert_eq!(VersionRange::major(12), VersionRange::major(9));
+        assert!(VersionRange::major(12).contains(HttpVersion::from_parts(9, 9)));
+
+        let range = VersionRange::between(v10, v2).unwrap();
+        assert!(!range.contains(v09));
+        assert!(range.contains(v10));
+        assert!(range.contains(HttpVersion::from_parts(1, 5)));
+        assert!(range.contains(v2));
+        assert!(!range.contains(HttpVersion::from_parts(2, 1)));
+        assert_eq!(range.min(), Some(v10));
+        assert_eq!(range.max(), Some(v2));
+
+        assert_eq!(VersionRange::between(v2, v10), Err(VersionRangeError::Reversed));
+        assert_eq!(VersionRange::between(v11, v11), Ok(VersionRange::exactly(v11)));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("*".parse(), Ok(VersionRange::ANY));
+        assert_eq!(">=1.1".parse(), Ok(VersionRange::at_least(HttpVersion::HTTP_11)));
+        assert_eq!("<=1.0".parse(), Ok(VersionRange::at_most(HttpVersion::HTTP_10)));
+        assert_eq!("2.0".parse(), Ok(VersionRange::exactly(HttpVersion::HTTP_2)));
+        assert_eq!("1.*".parse(), Ok(VersionRange::major(1)));
+        assert_eq!("1.0-2.0".parse(),
+                   VersionRange::between(HttpVersion::HTTP_10, HttpVersion::HTTP_2));
+
+        assert_eq!("2.0-1.0".parse::<VersionRange>(), Err(VersionRangeError::Reversed));
+        assert_eq!("".parse::<VersionRange>(),
+                   Err(VersionRangeError::Version(HttpVersionError::BadLength)));
+        assert_eq!(">1.1".parse::<VersionRange>(),
+                   Err(VersionRangeError::Version(HttpVersionError::BadLength)));
+        assert_eq!(">=1-1".parse::<VersionRange>(),
+                   Err(VersionRangeError::Version(HttpVersionError::BadSeparator)));
+        assert_eq!("x.*".parse::<VersionRange>(),
+                   Err(VersionRangeError::Version(HttpVersionError::BadDigit)));
+        assert_eq!("1.0-".parse::<VersionRange>(),
+                   Err(VersionRangeError::Version(HttpVersionError::BadLength)));
+        assert_eq!("HTTP/1.1".parse::<VersionRange>(),
+                   Err(VersionRangeError::Version(HttpVersionError::BadLength)));
+    }
+
+    #[test]
+    fn test_display() {
+        for &(s, expected) in [
+            ("*", "*"),
+            (">=1.1", ">=1.1"),
+            ("<=1.0", "<=1.0"),
+            ("2.0", "2.0"),
+            ("1.*", "1.*"),
+            ("1.0-2.0", "1.0-2.0"),
+            ("1.0-1.9", "1.0-1.9"),
+            ("1.1-1.1", "1.1"),
+        ].iter() {
+            let range: VersionRange = s.parse().unwrap();
+            assert_eq!(range.to_string(), expected);
+            assert_eq!(expected.parse(), Ok(range));
+        }
+
+        for major in 0..=255 {
+            let range = VersionRange::major(major);
+            assert_eq!(range.to_string().parse(), Ok(range));
+        }
+
+        let range = VersionRange::at_least(HttpVersion::from_parts(12, 34));
+        assert_eq!(range.to_string(), ">=12.34");
+        assert!(range.to_string().parse::<VersionRange>().is_err());
+    }
+}
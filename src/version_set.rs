@@ -0,0 +1,296 @@
+//! Sets of versions for expressing and negotiating supported versions.
+
+use core::fmt;
+use core::iter::{FromIterator, Rev};
+
+use HttpVersion;
+
+/// Set of versions with single-digit parts, stored as a bitmask without allocating.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct VersionSet {
+    /// Bit `10 * major + minor` is set for each version in the set.
+    bits: u128,
+}
+
+impl VersionSet {
+    /// Set containing no versions.
+    pub const EMPTY: VersionSet = VersionSet { bits: 0 };
+
+    /// Set containing HTTP/1.0 and HTTP/1.1.
+    pub const HTTP1X: VersionSet = VersionSet::EMPTY
+        .with(HttpVersion::HTTP_10)
+        .with(HttpVersion::HTTP_11);
+
+    /// Set containing every well-known version.
+    pub const KNOWN: VersionSet = VersionSet::EMPTY
+        .with(HttpVersion::HTTP_09)
+        .with(HttpVersion::HTTP_10)
+        .with(HttpVersion::HTTP_11)
+        .with(HttpVersion::HTTP_2)
+        .with(HttpVersion::HTTP_3);
+
+    /// Create a new, empty `VersionSet`.
+    pub const fn new() -> Self {
+        VersionSet::EMPTY
+    }
+
+    /// Return this set with the given version added, for building constant sets.
+    ///
+    /// Versions with a part that isn't a single digit can't be stored and are ignored.
+    pub const fn with(self, ver: HttpVersion) -> Self {
+        VersionSet { bits: self.bits | bit(ver) }
+    }
+
+    /// Add the given version to the set, returning whether it was newly added.
+    ///
+    /// Versions with a part that isn't a single digit can't be stored, so they're never
+    /// added and always return `false`.
+    pub fn insert(&mut self, ver: HttpVersion) -> bool {
+        let added = !self.contains(ver) && ver.is_valid();
+        self.bits |= bit(ver);
+        added
+    }
+
+    /// Remove the given version from the set, returning whether it was present.
+    pub fn remove(&mut self, ver: HttpVersion) -> bool {
+        let present = self.contains(ver);
+        self.bits &= !bit(ver);
+        present
+    }
+
+    /// Check if the set contains the given version.
+    pub const fn contains(&self, ver: HttpVersion) -> bool {
+        self.bits & bit(ver) != 0
+    }
+
+    /// Create the set of versions contained in both this set and the given set.
+    pub const fn intersection(&self, other: &VersionSet) -> VersionSet {
+        VersionSet { bits: self.bits & other.bits }
+    }
+
+    /// Create the set of versions contained in either this set or the given set.
+    pub const fn union(&self, other: &VersionSet) -> VersionSet {
+        VersionSet { bits: self.bits | other.bits }
+    }
+
+    /// Check if the set contains no versions.
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Retrieve the number of versions in the set.
+    pub const fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Retrieve the lowest version in the set, if any.
+    pub fn min(&self) -> Option<HttpVersion> {
+        self.iter().next()
+    }
+
+    /// Retrieve the highest version in the set, if any.
+    pub fn max(&self) -> Option<HttpVersion> {
+        self.iter().next_back()
+    }
+
+    /// Retrieve the highest version contained in both this set and the peer's set, if
+    /// they have any in common.
+    pub fn negotiate(&self, peer: &VersionSet) -> Option<HttpVersion> {
+        self.intersection(peer).max()
+    }
+
+    /// Create an iterator over the versions in the set, in ascending order.
+    pub fn iter(&self) -> VersionSetIter {
+        VersionSetIter { bits: self.bits }
+    }
+
+    /// Create an iterator over the versions in the set, in descending order, such as for
+    /// listing the most preferred versions first.
+    pub fn iter_desc(&self) -> Rev<VersionSetIter> {
+        self.iter().rev()
+    }
+}
+
+/// Get the bit representing the given version, or no bits if it can't be stored.
+const fn bit(ver: HttpVersion) -> u128 {
+    if ver.is_valid() {
+        1 << (ver.major * 10 + ver.minor)
+    } else {
+        0
+    }
+}
+
+/// Get the version represented by the given bit index.
+fn version(idx: u32) -> HttpVersion {
+    HttpVersion::from_parts(idx as u8 / 10, idx as u8 % 10)
+}
+
+/// Writes the versions of the set, such as `{HttpVersion { major: 1, minor: 1 }}`.
+impl fmt::Debug for VersionSet {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<HttpVersion> for VersionSet {
+    fn from_iter<I: IntoIterator<Item = HttpVersion>>(iter: I) -> Self {
+        let mut set = VersionSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<HttpVersion> for VersionSet {
+    fn extend<I: IntoIterator<Item = HttpVersion>>(&mut self, iter: I) {
+        for ver in iter {
+            self.insert(ver);
+        }
+    }
+}
+
+impl IntoIterator for &VersionSet {
+    type Item = HttpVersion;
+    type IntoIter = VersionSetIter;
+
+    fn into_iter(self) -> VersionSetIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for VersionSet {
+    type Item = HttpVersion;
+    type IntoIter = VersionSetIter;
+
+    fn into_iter(self) -> VersionSetIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the versions in a `VersionSet`, in ascending order.
+#[derive(Clone, Debug)]
+pub struct VersionSetIter {
+    /// Versions not yet yielded.
+    bits: u128,
+}
+
+impl Iterator for VersionSetIter {
+    type Item = HttpVersion;
+
+    fn next(&mut self) -> Option<HttpVersion> {
+        if self.bits == 0 {
+            return None;
+        }
+
+        let idx = self.bits.trailing_zeros();
+        self.bits &= self.bits - 1;
+
+        Some(version(idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for VersionSetIter {
+    fn next_back(&mut self) -> Option<HttpVersion> {
+        if self.bits == 0 {
+            return None;
+        }
+
+        let idx = 127 - self.bits.leading_zeros();
+        self.bits &= !(1 << idx);
+
+        Some(version(idx))
+    }
+}
+
+impl ExactSizeIterator for VersionSetIter {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_version_set() {
+        let mut set = VersionSet::new();
+        assert!(set.is_empty());
+        assert_eq!(set.len(), 0);
+        assert_eq!(set.max(), None);
+
+        assert!(set.insert(HttpVersion::HTTP_11));
+        assert!(!set.insert(HttpVersion::HTTP_11));
+        assert!(set.insert(HttpVersion::HTTP_10));
+        assert!(!set.insert(HttpVersion { major: 12, minor: 34 }));
+        assert!(!set.insert(HttpVersion { major: 1, minor: 10 }));
+        assert_eq!(set, VersionSet::HTTP1X);
+        assert_eq!(set.len(), 2);
+
+        assert!(set.contains(HttpVersion::HTTP_10));
+        assert!(set.contains(HttpVersion::HTTP_11));
+        assert!(!set.contains(HttpVersion::HTTP_2));
+        assert!(!set.contains(HttpVersion { major: 12, minor: 34 }));
+
+        assert!(set.remove(HttpVersion::HTTP_10));
+        assert!(!set.remove(HttpVersion::HTTP_10));
+        assert!(!set.remove(HttpVersion { major: 255, minor: 255 }));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [HttpVersion::HTTP_11]);
+
+        for major in 0..10 {
+            for minor in 0..10 {
+                let ver = HttpVersion::from_parts(major, minor);
+                let set = VersionSet::new().with(ver);
+
+                assert!(set.contains(ver));
+                assert_eq!(set.len(), 1);
+                assert_eq!(set.min(), Some(ver));
+                assert_eq!(set.max(), Some(ver));
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_ops() {
+        let server = VersionSet::KNOWN;
+        let client = VersionSet::HTTP1X;
+
+        assert_eq!(server.intersection(&client), VersionSet::HTTP1X);
+        assert_eq!(server.union(&client), VersionSet::KNOWN);
+        assert_eq!(server.negotiate(&client), Some(HttpVersion::HTTP_11));
+        assert_eq!(client.negotiate(&VersionSet::new().with(HttpVersion::HTTP_2)), None);
+        assert_eq!(VersionSet::KNOWN.min(), Some(HttpVersion::HTTP_09));
+        assert_eq!(VersionSet::KNOWN.max(), Some(HttpVersion::HTTP_3));
+    }
+
+    #[test]
+    fn test_iter() {
+        let set: VersionSet = HttpVersion::known().collect();
+        assert_eq!(set, VersionSet::KNOWN);
+
+        let vers: Vec<HttpVersion> = set.iter().collect();
+        assert_eq!(vers, HttpVersion::known().collect::<Vec<_>>());
+
+        let rev: Vec<HttpVersion> = set.iter().rev().collect();
+        assert_eq!(rev, HttpVersion::known().rev().collect::<Vec<_>>());
+
+        let desc: Vec<HttpVersion> = set.iter_desc().collect();
+        assert_eq!(desc, rev);
+
+        let mut iter = set.iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next(), Some(HttpVersion::HTTP_09));
+        assert_eq!(iter.next_back(), Some(HttpVersion::HTTP_3));
+        assert_eq!(iter.len(), 3);
+
+        let all: VersionSet = (0..100).map(|n| HttpVersion::from_parts(n / 10, n % 10)).collect();
+        assert_eq!(all.len(), 100);
+        assert!(all.iter().zip(all.iter().skip(1)).all(|(a, b)| a < b));
+        assert_eq!(all.max(), Some(HttpVersion::from_parts(9, 9)));
+        assert!(all.iter_desc().eq(all.iter().collect::<Vec<_>>().into_iter().rev()));
+
+        assert_eq!(format!("{:?}", VersionSet::EMPTY), "{}");
+        assert_eq!(format!("{:?}", VersionSet::new().with(HttpVersion::HTTP_2)),
+                   "{HttpVersion { major: 2, minor: 0 }}");
+    }
+}
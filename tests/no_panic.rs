@@ -0,0 +1,114 @@
+//! Checks that the parsing and encoding entry points can't panic on any input.
+//!
+//! Each entry point is wrapped in a `#[no_panic]` function, which fails to link if the
+//! optimizer can't prove the wrapped call never panics. The proof relies on
+//! optimizations, so these checks only run with `cargo test --release`.
+
+#![cfg(not(debug_assertions))]
+
+extern crate no_panic;
+extern crate uhttp_version;
+
+use std::hint::black_box;
+
+use no_panic::no_panic;
+use uhttp_version::{HttpVersion, HttpVersionError, ParseOptions, ParseStatus, SpanError,
+                    StartLine, StartLineError, StartLineSpans, VersionParser, VersionRange,
+                    VersionRangeError};
+
+#[no_panic]
+fn from_bytes(s: &[u8]) -> Result<HttpVersion, HttpVersionError> {
+    HttpVersion::from_bytes(s)
+}
+
+#[no_panic]
+fn from_bytes_prefix(s: &[u8]) -> Result<(HttpVersion, &[u8]), HttpVersionError> {
+    HttpVersion::from_bytes_prefix(s)
+}
+
+#[no_panic]
+fn from_line(s: &[u8]) -> Result<(HttpVersion, bool), HttpVersionError> {
+    HttpVersion::from_line(s)
+}
+
+#[no_panic]
+fn from_line_counting(s: &[u8]) -> Result<(HttpVersion, usize), HttpVersionError> {
+    HttpVersion::from_line_counting(s)
+}
+
+#[no_panic]
+fn from_request_line_tail(s: &[u8]) -> Result<HttpVersion, HttpVersionError> {
+    HttpVersion::from_request_line_tail(s)
+}
+
+#[no_panic]
+fn parse_lenient(s: &[u8]) -> Result<(HttpVersion, bool), HttpVersionError> {
+    ParseOptions::LENIENT.parse_line(s)
+}
+
+#[no_panic]
+fn push(p: &mut VersionParser, s: &[u8]) -> Result<ParseStatus, HttpVersionError> {
+    p.push(s)
+}
+
+#[no_panic]
+fn start_line(s: &[u8]) -> Result<HttpVersion, StartLineError> {
+    StartLine::from_bytes(s).map(|line| line.version())
+}
+
+#[no_panic]
+fn spans(s: &[u8]) -> Result<StartLineSpans, SpanError> {
+    StartLineSpans::from_request_line(s)
+}
+
+#[no_panic]
+fn version_range(s: &[u8]) -> Result<VersionRange, VersionRangeError> {
+    VersionRange::from_bytes(s)
+}
+
+#[no_panic]
+fn from_parts(major: u8, minor: u8) -> HttpVersion {
+    HttpVersion::from_parts(major, minor)
+}
+
+#[no_panic]
+fn encode(ver: HttpVersion, buf: &mut [u8]) -> Result<usize, HttpVersionError> {
+    ver.encode(buf)
+}
+
+#[no_panic]
+fn from_u16(packed: u16) -> Result<HttpVersion, HttpVersionError> {
+    HttpVersion::from_u16(packed)
+}
+
+#[test]
+fn test_no_panic() {
+    let inputs = [&b""[..], b"HTTP/1.1", b"HTTP/1.1\r\n", b" http/1.0\n", b"HTTP/12.34",
+                  b"HTTP/1.1 200 OK", b"\r\n"];
+
+    for &s in inputs.iter() {
+        let s = black_box(s);
+
+        assert_eq!(from_bytes(s), HttpVersion::from_bytes(s));
+        assert_eq!(from_bytes_prefix(s), HttpVersion::from_bytes_prefix(s));
+        assert_eq!(from_line(s), HttpVersion::from_line(s));
+        assert_eq!(from_line_counting(s), HttpVersion::from_line_counting(s));
+        assert_eq!(from_request_line_tail(s), HttpVersion::from_request_line_tail(s));
+        assert_eq!(parse_lenient(s), ParseOptions::LENIENT.parse_line(s));
+
+        assert_eq!(start_line(s), StartLine::from_bytes(s).map(|line| line.version()));
+        assert_eq!(spans(s), StartLineSpans::from_request_line(s));
+        assert_eq!(version_range(s), VersionRange::from_bytes(s));
+
+        let (mut a, mut b) = (VersionParser::new(), VersionParser::new());
+        assert_eq!(push(&mut a, s), b.push(s));
+    }
+
+    let ver = from_parts(black_box(12), black_box(34));
+    assert_eq!(ver, HttpVersion { major: 12, minor: 34 });
+
+    let mut buf = [0; 8];
+    assert_eq!(encode(ver, black_box(&mut buf[..])), Ok(8));
+    assert_eq!(encode(ver, black_box(&mut buf[..4])), Err(HttpVersionError::BufferTooSmall));
+    assert_eq!(from_u16(black_box(0x0101)), HttpVersion::from_u16(0x0101));
+}
@@ -0,0 +1,84 @@
+//! Property tests for parsing and formatting.
+
+extern crate proptest;
+extern crate uhttp_version;
+
+use proptest::prelude::*;
+use uhttp_version::{HttpVersion, ParseStatus, StartLine, StatusLine, VersionParser};
+
+proptest! {
+    #[test]
+    fn format_then_parse(major in 0u8..10, minor in 0u8..10) {
+        let ver = HttpVersion::from_parts(major, minor);
+        let s = ver.to_string();
+
+        prop_assert_eq!(HttpVersion::from_bytes(s.as_bytes()), Ok(ver));
+        prop_assert_eq!(s.parse::<HttpVersion>(), Ok(ver));
+        prop_assert_eq!(HttpVersion::from_bytes(&ver.to_array()), Ok(ver));
+        prop_assert_eq!(HttpVersion::from_u16(ver.to_u16()), Ok(ver));
+    }
+
+    #[test]
+    fn parse_then_format(s in "HTTP/[0-9]\\.[0-9]") {
+        let ver = HttpVersion::from_bytes(s.as_bytes()).unwrap();
+        prop_assert_eq!(ver.to_string(), s);
+    }
+
+    #[test]
+    fn from_bytes_never_panics(data in proptest::collection::vec(any::<u8>(), 0..16)) {
+        let res = HttpVersion::from_bytes(&data);
+
+        prop_assert_eq!(HttpVersion::from_bytes_const(&data), res);
+
+        if let Ok(ver) = res {
+            prop_assert!(ver.is_valid());
+            prop_assert_eq!(&ver.to_array()[..], &data[..]);
+        }
+    }
+
+    #[test]
+    fn prefix_matches_chunks(data in proptest::collection::vec(any::<u8>(), 0..16),
+                             split in 0usize..16)
+    {
+        let res = HttpVersion::from_bytes_prefix(&data).map(|(ver, _)| ver);
+        let (a, b) = data.split_at(split.min(data.len()));
+
+        prop_assert_eq!(HttpVersion::from_chunks(vec![a, b]), res);
+    }
+
+    #[test]
+    fn incremental_matches_whole(s in "HTTP/[0-9]\\.[0-9]", split in 0usize..=8) {
+        let (a, b) = s.as_bytes().split_at(split);
+        let mut parser = VersionParser::new();
+
+        let status = match parser.push(a) {
+            Ok(ParseStatus::NeedMore) => parser.push(b),
+            status => status,
+        };
+
+        match status {
+            Ok(ParseStatus::Done { version, .. }) => {
+                prop_assert_eq!(Ok(version), HttpVersion::from_bytes(s.as_bytes()));
+            },
+            status => prop_assert!(false, "unexpected status {:?}", status),
+        }
+    }
+
+    #[test]
+    fn start_line_never_panics(data in proptest::collection::vec(any::<u8>(), 0..64)) {
+        let _ = StartLine::from_bytes(&data);
+    }
+
+    #[test]
+    fn status_line_round_trip(major in 0u8..10, minor in 0u8..10, code in 100u16..600,
+                              reason in "[ -~]{0,20}")
+    {
+        let s = format!("HTTP/{}.{} {} {}", major, minor, code, reason);
+        let line = StatusLine::from_bytes(s.as_bytes()).unwrap();
+
+        prop_assert_eq!(line.version, HttpVersion::from_parts(major, minor));
+        prop_assert_eq!(line.code.as_u16(), code);
+        prop_assert_eq!(line.reason, reason.as_bytes());
+        prop_assert_eq!(line.to_string(), s);
+    }
+}